@@ -0,0 +1,40 @@
+// Toggles a pin 100k times with and without caching of the value file.
+//
+// Needs a Jetson board with BOARD pin 7 free to use as an output:
+//
+//     cargo bench --bench toggle
+
+use jetson_gpio::{Direction, Level, Mode, GPIO};
+use std::time::Instant;
+
+const PIN: u32 = 7;
+const ITERATIONS: u32 = 100_000;
+
+fn toggle(gpio: &mut GPIO) -> f64 {
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        let level = if i % 2 == 0 { Level::HIGH } else { Level::LOW };
+        gpio.output(vec![PIN], vec![level]).unwrap();
+    }
+    start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    let mut gpio = GPIO::new();
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![PIN], Direction::OUT, None, Some(Level::LOW)).unwrap();
+
+    for caching in [false, true] {
+        gpio.set_fd_caching(caching).unwrap();
+        let elapsed = toggle(&mut gpio);
+        println!(
+            "fd caching {:>5}: {} toggles in {:.3} s ({:.2} us per toggle)",
+            caching,
+            ITERATIONS,
+            elapsed,
+            elapsed * 1_000_000.0 / ITERATIONS as f64
+        );
+    }
+
+    gpio.cleanup_all().unwrap();
+}
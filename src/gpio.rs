@@ -1,16 +1,76 @@
 use anyhow::Error;
 use std::{
-    collections::HashMap,
-    fs,
-    io::{Read, Seek, Write},
-    path::Path,
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::{self, File},
+    io::{self, Read, Seek, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use crate::gpio_pin_data::{get_data, ChannelInfo, JetsonInfo, Mode};
+use crate::gpio_pin_data::{
+    get_data, get_mock_data, pwm_max_frequency, BoardData, Channel, ChannelDataByMode, ChannelInfo,
+    GpioChip, JetsonInfo, Mode, PinAliases, PinMapEntry,
+};
+use crate::gpio_builder::GpioBuilder;
+use crate::gpio_cdev::{
+    get_line_value, get_line_values, release_request_line, request_lines, set_line_debounce, set_line_value,
+    set_line_values, set_request_line, LineRequest, RequestedLine,
+};
+#[cfg(feature = "tokio")]
+use crate::gpio_event::{async_wait_for_edge, EdgeStream};
+use crate::gpio_event::{
+    blocking_count_edges, blocking_wait_for_edge, blocking_wait_for_level, Edge, EventCallback, EventDetector,
+};
+use crate::gpio_mock::MockPins;
+use crate::gpio_paths::{set_paths, sysfs_gpio_root, GpioPaths};
+use crate::gpio_led::Led;
+use crate::gpio_pin::{InputPin, OutputPin, PinIo};
+use crate::gpio_pwm::{disable_pwm, unexport_pwm, PwmFrequencyLimit, PWM};
+use crate::gpio_servo::{Servo, ServoConfig};
+use crate::gpio_shared::SharedGpio;
+use crate::gpio_spi::{BitBangSpi, BitOrder, SpiMode};
+use crate::gpio_watchdog::{SafeState, Watchdog};
+
+// default interval between reads of an input in the polling helpers
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+// default time to wait for the files of an exported sysfs channel to appear
+const DEFAULT_EXPORT_TIMEOUT: Duration = Duration::from_secs(1);
+
+// default retries of a failed sysfs export or unexport
+const DEFAULT_EXPORT_RETRY: RetryPolicy = RetryPolicy {
+    retries: 3,
+    backoff: Duration::from_millis(100),
+};
+
+// The backend used for the requested one. With the `stub` feature, every object uses the
+// in-memory pins of the mock backend, so programs built for a Jetson also run elsewhere.
+pub(crate) fn effective_backend(backend: Backend) -> Backend {
+    match cfg!(feature = "stub") {
+        true => Backend::MOCK,
+        false => backend,
+    }
+}
+
+// The error for a feature the mock backend does not simulate. With the `stub` feature the
+// mock backend replaces the requested one, so the message names the feature instead.
+fn unsupported_by_mock(feature: &str) -> Error {
+    match cfg!(feature = "stub") {
+        true => Error::msg(format!("{} is not supported with the stub feature", feature)),
+        false => Error::msg(format!("{} is not supported by the mock backend", feature)),
+    }
+}
+
+/// A function called with the message of every warning, set with `GPIO::set_warning_handler`.
+pub type WarningHandler = Arc<dyn Fn(&str) + Send + Sync>;
 
-static SYSFS_ROOT: &str = "/sys/class/gpio";
+// The default warning handler, which prints the message to stdout.
+pub(crate) fn print_warning(message: &str) {
+    println!("{}", message);
+}
 
 /// Specifies the GPIO pin value in output mode.
 ///
@@ -28,20 +88,104 @@ static SYSFS_ROOT: &str = "/sys/class/gpio";
 /// let mut gpio = GPIO::new();
 /// gpio.setmode(Mode::BOARD).unwrap();
 ///
-/// gpio.setup(vec![7, 11], Direction::OUT, None).unwrap();
+/// gpio.setup(vec![7, 11], Direction::OUT, None, None).unwrap();
 /// gpio.output(vec![7, 11], vec![Level::HIGH, Level::LOW]).unwrap();
 /// ```
-#[derive(PartialEq, Clone)]
+///
+/// A `Level` converts from and into a `bool`, where `true` is `HIGH`:
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Level, Direction, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+/// let led_on = true;
+/// gpio.output_one(7, led_on.into()).unwrap();
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Level {
     LOW = 0,
     HIGH = 1,
 }
 
+impl From<bool> for Level {
+    fn from(value: bool) -> Self {
+        if value {
+            Level::HIGH
+        } else {
+            Level::LOW
+        }
+    }
+}
+
+impl From<Level> for bool {
+    fn from(level: Level) -> Self {
+        level == Level::HIGH
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl Level {
+    /// Converts a string to a `Level` enum.
+    ///
+    /// Valid strings are `"LOW"` and `"HIGH"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::Level;
+    ///
+    /// assert_eq!(Level::from_str("HIGH").unwrap(), Level::HIGH);
+    /// assert!(Level::from_str("ON").is_err());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Level, Error> {
+        match s {
+            "LOW" => Ok(Level::LOW),
+            "HIGH" => Ok(Level::HIGH),
+            _ => Err(Error::msg(format!("Invalid level: {}", s))),
+        }
+    }
+
+    /// Converts a `Level` enum to a string, `"LOW"` or `"HIGH"`.
+    pub fn to_str(&self) -> &str {
+        match self {
+            Level::LOW => "LOW",
+            Level::HIGH => "HIGH",
+        }
+    }
+
+    // The contents of a sysfs `value` file that represent this level. Every write of a
+    // level to sysfs goes through here, so a new variant only needs to be handled once.
+    pub(crate) fn sysfs_str(&self) -> &'static str {
+        match self {
+            Level::LOW => "0",
+            Level::HIGH => "1",
+        }
+    }
+
+    pub(crate) fn inverted(&self) -> Level {
+        match self {
+            Level::LOW => Level::HIGH,
+            Level::HIGH => Level::LOW,
+        }
+    }
+}
+
 /// Specifies the GPIO pin direction.
 ///
 /// * `IN` - Input
 /// * `OUT` - Output
 /// * `HARD_PWM` - Hardware PWM output
+/// * `TRISTATE` - Output switched to high impedance with `GPIO::set_tristate`
 /// * `UNKNOWN` - Unknown direction for GPIOs that are not yet setup
 ///
 /// # Example
@@ -54,17 +198,67 @@ pub enum Level {
 ///
 /// let mut gpio = GPIO::new();
 ///
-/// gpio.setup(vec![7], Direction::OUT, None).unwrap();
+/// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
 /// ```
-#[derive(PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     UNKNOWN = -1,
     OUT = 0,
     IN = 1,
+    TRISTATE = 2,
     HARD_PWM = 43,
 }
 
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
 impl Direction {
+    /// Converts a string to a `Direction` enum.
+    ///
+    /// Valid strings are:
+    ///
+    /// * `"IN"`
+    /// * `"OUT"`
+    /// * `"HARD_PWM"`
+    /// * `"TRISTATE"`
+    /// * `"UNKNOWN"`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::from_str("OUT").unwrap(), None, None).unwrap();
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Direction, Error> {
+        match s {
+            "UNKNOWN" => Ok(Direction::UNKNOWN),
+            "OUT" => Ok(Direction::OUT),
+            "IN" => Ok(Direction::IN),
+            "HARD_PWM" => Ok(Direction::HARD_PWM),
+            "TRISTATE" => Ok(Direction::TRISTATE),
+            _ => Err(Error::msg(format!("Invalid direction: {}", s))),
+        }
+    }
+
+    /// Converts a `Direction` enum to a string, e.g. `"OUT"`.
+    pub fn to_str(&self) -> &str {
+        match self {
+            Direction::UNKNOWN => "UNKNOWN",
+            Direction::OUT => "OUT",
+            Direction::IN => "IN",
+            Direction::HARD_PWM => "HARD_PWM",
+            Direction::TRISTATE => "TRISTATE",
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
         match self {
             Direction::OUT => true,
@@ -74,12 +268,252 @@ impl Direction {
     }
 }
 
-fn check_write_access() -> Result<(), Error> {
-    let export_path = format!("{}/export", SYSFS_ROOT);
-    let unexport_path = format!("{}/unexport", SYSFS_ROOT);
+/// Specifies the internal pull resistor of an input.
+///
+/// * `OFF` - No pull resistor (default)
+/// * `DOWN` - Pull-down resistor
+/// * `UP` - Pull-up resistor
+///
+/// The bias can only be set with `Backend::CHARDEV`. The sysfs interface has no
+/// way to configure it, so a warning is printed and the pin keeps the bias set
+/// by the pinmux configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Backend, Direction, Mode, Pull};
+///
+/// let mut gpio = GPIO::new_with_backend(Backend::CHARDEV);
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// gpio.setup(vec![7], Direction::IN, Pull::UP, None).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pull {
+    OFF = 20,
+    DOWN = 21,
+    UP = 22,
+}
+
+/// Specifies how an output drives its pin.
+///
+/// * `PUSH_PULL` - The pin is driven both HIGH and LOW (default)
+/// * `OPEN_DRAIN` - The pin is driven LOW and floats for HIGH, so an external
+///   pull-up resistor sets the level and other devices can pull the line low
+///
+/// The character device backend configures the line as open-drain in the
+/// kernel. The sysfs and mock backends emulate it by switching the pin to an
+/// input for HIGH and back to an output driving LOW. The mock backend reads a
+/// floating line as `HIGH`, as if it had a pull-up resistor.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Drive, Level, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// // a line shared with other open-drain devices, released until it is used
+/// gpio.setup_output_with_drive(vec![7], Drive::OPEN_DRAIN, None).unwrap();
+/// gpio.output(vec![7], vec![Level::LOW]).unwrap();
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Drive {
+    PUSH_PULL,
+    OPEN_DRAIN,
+}
+
+/// Specifies the kernel interface used to control the GPIO pins.
+///
+/// * `SYSFS` - The legacy `/sys/class/gpio` interface (default)
+/// * `CHARDEV` - The GPIO character devices `/dev/gpiochipN`, required on kernels without the sysfs interface
+/// * `MOCK` - In-memory pins that need no hardware, for testing code that uses this crate
+///
+/// Hardware PWM uses the PWM sysfs interface with the sysfs and character device backends.
+///
+/// The mock backend simulates the pins of the board it runs on, or of a Jetson
+/// Nano elsewhere; set the `JETSON_MODEL_NAME` environment variable to simulate
+/// another model. Outputs keep the last written level and inputs read `LOW`, or
+/// `HIGH` with `Pull::UP`, until `set_mock_input` drives them. Edge detection,
+/// the watchdog and PWM are not simulated.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Backend, Direction, Mode};
+///
+/// let mut gpio = GPIO::new_with_backend(Backend::CHARDEV);
+/// gpio.setmode(Mode::BOARD).unwrap();
+/// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Copy)]
+pub enum Backend {
+    SYSFS,
+    CHARDEV,
+    MOCK,
+}
+
+/// The kind of an error returned by the methods of `GPIO` when a call is not valid
+/// in the current state, e.g. because the pin numbering mode is not set or a
+/// channel has not been set up.
+///
+/// It is wrapped in the returned `anyhow::Error` and can be told apart from other
+/// errors with `downcast_ref`. Other failures of the kernel interfaces are
+/// reported as the underlying I/O error.
+///
+/// * `MODE_NOT_SET` - `setmode` has not been called
+/// * `INVALID_CHANNEL` - The channel does not exist in the pin numbering mode
+/// * `NOT_A_GPIO` - The channel exists but cannot be used as a GPIO
+/// * `NOT_A_PWM` - The channel exists but has no PWM controller
+/// * `NOT_CONFIGURED` - The channel has not been set up
+/// * `WRONG_DIRECTION` - The channel is not set up with the direction the call needs
+/// * `NO_WRITE_ACCESS` - The GPIO interface is present, but not writable
+/// * `NO_INTERFACE` - The GPIO interface at the given path is not present or cannot be opened
+/// * `CHANNEL_BUSY` - The kernel refuses to export or request the channel because it is already
+///   in use, e.g. claimed by a device-tree overlay, a GPIO hog or another process
+/// * `EXPORT_FAILED` - A sysfs export or unexport still failed after every retry of the
+///   `RetryPolicy`, wrapped around the error of the last attempt
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, GpioError, Level, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// if let Err(e) = gpio.output_one(7, Level::HIGH) {
+///     match e.downcast_ref::<GpioError>() {
+///         Some(GpioError::WRONG_DIRECTION(channel, _)) => eprintln!("Channel {} is not an output", channel),
+///         Some(GpioError::CHANNEL_BUSY(channel)) => eprintln!("Channel {} is used by another driver", channel),
+///         _ => eprintln!("{}", e),
+///     }
+/// }
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GpioError {
+    MODE_NOT_SET,
+    INVALID_CHANNEL(Channel),
+    NOT_A_GPIO(Channel),
+    NOT_A_PWM(Channel),
+    NOT_CONFIGURED(Channel),
+    WRONG_DIRECTION(Channel, Direction),
+    NO_WRITE_ACCESS,
+    NO_INTERFACE(String),
+    CHANNEL_BUSY(Channel),
+    EXPORT_FAILED {
+        channel: Channel,
+        unexport: bool,
+        attempts: u32,
+    },
+}
+
+impl fmt::Display for GpioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GpioError::MODE_NOT_SET => write!(
+                f,
+                "Please set pin numbering mode using GPIO.setmode(Mode::BOARD), GPIO.setmode(Mode::BCM), GPIO.setmode(Mode::TEGRA_SOC) or GPIO.setmode(Mode::CVM)"
+            ),
+            GpioError::INVALID_CHANNEL(channel) => write!(f, "The channel sent is invalid: {}", channel),
+            GpioError::NOT_A_GPIO(channel) => write!(f, "Channel {} is not a GPIO", channel),
+            GpioError::NOT_A_PWM(channel) => write!(f, "Channel {} is not a PWM", channel),
+            GpioError::NOT_CONFIGURED(_) => write!(f, "You must setup() the GPIO channel first"),
+            GpioError::WRONG_DIRECTION(_, Direction::OUT) => {
+                write!(f, "The GPIO channel has not been set up as an OUTPUT")
+            }
+            GpioError::WRONG_DIRECTION(_, Direction::IN) => {
+                write!(f, "You must setup() the GPIO channel as an input first")
+            }
+            GpioError::WRONG_DIRECTION(channel, direction) => {
+                write!(f, "Channel {} has not been set up as {}", channel, direction)
+            }
+            GpioError::NO_WRITE_ACCESS => write!(
+                f,
+                "You do not have write access to the GPIO interface. Run as root or add the user to the gpio group."
+            ),
+            GpioError::NO_INTERFACE(path) => write!(
+                f,
+                "The GPIO interface {} is not present or cannot be opened. Kernels without the sysfs interface need Backend::CHARDEV.",
+                path
+            ),
+            GpioError::CHANNEL_BUSY(channel) => write!(
+                f,
+                "Channel {} is already in use (e.g. by a device-tree overlay, a GPIO hog or another process) and cannot be controlled",
+                channel
+            ),
+            GpioError::EXPORT_FAILED {
+                channel,
+                unexport,
+                attempts,
+            } => {
+                let operation = if *unexport { "unexport" } else { "export" };
+                write!(f, "Failed to {} channel {} after {} attempts", operation, channel, attempts)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GpioError {}
+
+/// How often a failed sysfs export or unexport of a channel is retried, set with
+/// `GPIO::set_export_retry` or `GpioBuilder::export_retry`.
+///
+/// Right after boot, udev may not have settled yet, so exporting a channel can
+/// fail or its files can appear late. The n-th retry waits n times `backoff`
+/// before trying again. A channel claimed by the kernel (`GpioError::CHANNEL_BUSY`)
+/// is never retried. The default is 3 retries with a backoff of 100 ms.
+///
+/// * `retries` - The number of attempts after the first one, 0 to disable retries
+/// * `backoff` - The base delay between two attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        DEFAULT_EXPORT_RETRY
+    }
+}
+
+// Runs a sysfs export or unexport until it succeeds or the retries are exhausted.
+fn with_retries<F>(ch_info: &ChannelInfo, unexport: bool, retry: RetryPolicy, mut f: F) -> Result<(), Error>
+where
+    F: FnMut() -> Result<(), Error>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match f() {
+            Ok(()) => return Ok(()),
+            Err(e) if matches!(e.downcast_ref::<GpioError>(), Some(GpioError::CHANNEL_BUSY(_))) => return Err(e),
+            Err(e) if attempts > retry.retries => {
+                return Err(e.context(GpioError::EXPORT_FAILED {
+                    channel: ch_info.channel.clone(),
+                    unexport,
+                    attempts,
+                }))
+            }
+            Err(_) => thread::sleep(retry.backoff * attempts),
+        }
+    }
+}
+
+fn check_sysfs_write_access() -> Result<(), GpioError> {
+    let export_path = format!("{}/export", sysfs_gpio_root());
+    let unexport_path = format!("{}/unexport", sysfs_gpio_root());
 
-    let export_metadata = fs::metadata(&export_path).unwrap();
-    let unexport_metadata = fs::metadata(&unexport_path).unwrap();
+    let (Ok(export_metadata), Ok(unexport_metadata)) = (fs::metadata(&export_path), fs::metadata(&unexport_path))
+    else {
+        return Err(GpioError::NO_INTERFACE(sysfs_gpio_root()));
+    };
 
     let export_permissions = export_metadata.permissions();
     let unexport_permissions = unexport_metadata.permissions();
@@ -87,7 +521,7 @@ fn check_write_access() -> Result<(), Error> {
     if !export_permissions.readonly() && !unexport_permissions.readonly() {
         Ok(())
     } else {
-        Err(Error::msg("You do not have write access to the GPIO sysfs interface."))
+        Err(GpioError::NO_WRITE_ACCESS)
     }
 }
 
@@ -102,12 +536,13 @@ fn sysfs_channel_configuration(ch_info: ChannelInfo) -> Option<Direction> {
         }
     }
 
-    let gpio_dir = format!("{}/{}", SYSFS_ROOT, ch_info.global_gpio_name);
+    let gpio_dir = format!("{}/{}", sysfs_gpio_root(), ch_info.global_gpio_name);
     if !Path::new(&gpio_dir).exists() {
         return None;
     }
 
-    let gpio_direction = fs::read_to_string(format!("{}/direction", gpio_dir)).unwrap();
+    let gpio_direction = fs::read_to_string(format!("{}/direction", gpio_dir)).ok()?;
+    let gpio_direction = gpio_direction.trim();
     if gpio_direction == "in" {
         return Some(Direction::IN);
     } else if gpio_direction == "out" {
@@ -117,66 +552,211 @@ fn sysfs_channel_configuration(ch_info: ChannelInfo) -> Option<Direction> {
     None
 }
 
-fn export_gpio(ch_info: ChannelInfo) {
-    let gpio_dir = format!("{}/{}", SYSFS_ROOT, ch_info.global_gpio_name);
+fn export_gpio(ch_info: ChannelInfo, timeout: Duration, retry: RetryPolicy) -> Result<(), Error> {
+    with_retries(&ch_info, false, retry, || export_gpio_once(&ch_info, timeout))
+}
+
+fn export_gpio_once(ch_info: &ChannelInfo, timeout: Duration) -> Result<(), Error> {
+    let gpio_dir = format!("{}/{}", sysfs_gpio_root(), ch_info.global_gpio_name);
     if !Path::new(&gpio_dir).exists() {
         let mut f_export = fs::OpenOptions::new()
             .write(true)
-            .open(format!("{}/export", SYSFS_ROOT))
-            .unwrap();
-        f_export
-            .write_all(ch_info.global_gpio.to_string().as_bytes())
-            .unwrap();
+            .open(format!("{}/export", sysfs_gpio_root()))
+            .map_err(|e| Error::new(e).context(format!("Failed to export channel {}", ch_info.channel)))?;
+        if let Err(e) = f_export.write_all(ch_info.global_gpio.to_string().as_bytes()) {
+            // the kernel also reports EBUSY if another process exported the channel
+            // in the meantime, which is harmless. Otherwise, it has requested the
+            // line itself, which is the case for GPIO hogs defined in the device tree.
+            if e.kind() == io::ErrorKind::ResourceBusy {
+                if !Path::new(&gpio_dir).exists() {
+                    return Err(Error::new(GpioError::CHANNEL_BUSY(ch_info.channel.clone())));
+                }
+            } else {
+                return Err(Error::new(e).context(format!("Failed to export channel {}", ch_info.channel)));
+            }
+        }
     }
 
+    let deadline = Instant::now() + timeout;
     while !Path::new(&format!("{}/value", gpio_dir)).exists() {
+        if Instant::now() >= deadline {
+            return Err(Error::msg(format!(
+                "Timed out waiting for {} to export (channel {})",
+                ch_info.global_gpio_name, ch_info.channel
+            )));
+        }
         thread::sleep(Duration::from_millis(10));
     }
+
+    Ok(())
+}
+
+fn unexport_gpio(ch_info: ChannelInfo, retry: RetryPolicy) -> Result<(), Error> {
+    with_retries(&ch_info, true, retry, || unexport_gpio_once(&ch_info))
 }
 
-fn unexport_gpio(ch_info: ChannelInfo) {
-    let gpio_dir = format!("{}/{}", SYSFS_ROOT, ch_info.global_gpio_name);
+fn unexport_gpio_once(ch_info: &ChannelInfo) -> Result<(), Error> {
+    let gpio_dir = format!("{}/{}", sysfs_gpio_root(), ch_info.global_gpio_name);
     if Path::new(&gpio_dir).exists() {
         let mut f_unexport = fs::OpenOptions::new()
             .write(true)
-            .open(format!("{}/unexport", SYSFS_ROOT))
-            .unwrap();
-        f_unexport
-            .write_all(ch_info.global_gpio.to_string().as_bytes())
-            .unwrap();
+            .open(format!("{}/unexport", sysfs_gpio_root()))?;
+        f_unexport.write_all(ch_info.global_gpio.to_string().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+fn write_direction(ch_info: ChannelInfo, direction: String) -> Result<(), Error> {
+    let gpio_dir = format!("{}/{}/direction", sysfs_gpio_root(), ch_info.global_gpio_name);
+    let mut f_direction = fs::OpenOptions::new().write(true).open(gpio_dir)?;
+    f_direction.rewind()?;
+    f_direction.write_all(direction.as_bytes())?;
+    Ok(())
+}
+
+// The sysfs direction that switches a channel to an output. "high" and "low" set the
+// direction and the initial level in one step, so the pin never drives an undefined
+// level in between, as writing "out" and then the value would.
+fn output_direction(initial: Option<Level>) -> &'static str {
+    match initial {
+        Some(Level::HIGH) => "high",
+        Some(Level::LOW) => "low",
+        None => "out",
+    }
+}
+
+fn write_value(ch_info: ChannelInfo, value: String) -> Result<(), Error> {
+    let gpio_dir = format!("{}/{}/value", sysfs_gpio_root(), ch_info.global_gpio_name);
+    let mut f_direction = fs::OpenOptions::new().write(true).open(gpio_dir)?;
+    f_direction.rewind()?;
+    f_direction.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+fn read_value(ch_info: ChannelInfo) -> Result<String, Error> {
+    let gpio_dir = format!("{}/{}/value", sysfs_gpio_root(), ch_info.global_gpio_name);
+    let mut f_direction = fs::OpenOptions::new().read(true).open(gpio_dir)?;
+    let mut value = String::new();
+    f_direction.rewind()?;
+    f_direction.read_to_string(&mut value)?;
+    Ok(value.trim().to_string())
+}
+
+// Reads the level of a channel exported through sysfs, or `None` if it is not readable.
+fn read_sysfs_level(ch_info: &ChannelInfo) -> Option<Level> {
+    let gpio_dir = format!("{}/{}/value", sysfs_gpio_root(), ch_info.global_gpio_name);
+    match fs::read_to_string(gpio_dir).ok()?.trim() {
+        "0" => Some(Level::LOW),
+        "1" => Some(Level::HIGH),
+        _ => None,
     }
 }
 
-fn write_direction(ch_info: ChannelInfo, direction: String) {
-    let gpio_dir = format!("{}/{}/direction", SYSFS_ROOT, ch_info.global_gpio_name);
-    let mut f_direction = fs::OpenOptions::new().write(true).open(gpio_dir).unwrap();
-    f_direction.rewind().unwrap();
-    f_direction.write_all(direction.as_bytes()).unwrap();
+pub(crate) fn open_value_file(ch_info: &ChannelInfo) -> io::Result<File> {
+    let gpio_dir = format!("{}/{}/value", sysfs_gpio_root(), ch_info.global_gpio_name);
+    fs::OpenOptions::new().read(true).write(true).open(gpio_dir)
 }
 
-fn write_value(ch_info: ChannelInfo, value: String) {
-    let gpio_dir = format!("{}/{}/value", SYSFS_ROOT, ch_info.global_gpio_name);
-    let mut f_direction = fs::OpenOptions::new().write(true).open(gpio_dir).unwrap();
-    f_direction.rewind().unwrap();
-    f_direction.write_all(value.as_bytes()).unwrap();
+fn write_value_file(mut f_value: &File, value: String) -> Result<(), Error> {
+    f_value.rewind()?;
+    f_value.write_all(value.as_bytes())?;
+    Ok(())
 }
 
-fn read_value(ch_info: ChannelInfo) -> String {
-    let gpio_dir = format!("{}/{}/value", SYSFS_ROOT, ch_info.global_gpio_name);
-    let mut f_direction = fs::OpenOptions::new().read(true).open(gpio_dir).unwrap();
+pub(crate) fn read_value_file(mut f_value: &File) -> io::Result<String> {
     let mut value = String::new();
-    f_direction.rewind().unwrap();
-    f_direction.read_to_string(&mut value).unwrap();
-    value
+    f_value.rewind()?;
+    f_value.read_to_string(&mut value)?;
+    Ok(value.trim().to_string())
 }
 
-fn output_one(ch_info: ChannelInfo, value: Level) {
-    let value_str = match value {
-        Level::HIGH => "1",
-        Level::LOW => "0",
+// Emulates an open-drain output through sysfs: the pin floats as an input for HIGH
+// and is switched to an output driving LOW for LOW.
+fn write_open_drain(ch_info: ChannelInfo, value: Level) -> Result<(), Error> {
+    let direction = match value {
+        Level::HIGH => "in",
+        Level::LOW => "low",
     };
+    write_direction(ch_info, direction.to_string())
+}
+
+pub(crate) fn output_one(ch_info: ChannelInfo, value: Level) -> Result<(), Error> {
+    write_value(ch_info, value.sysfs_str().to_string())
+}
+
+/// The configuration of a single channel as recorded in a `ConfigSnapshot`.
+///
+/// The fields are:
+/// * `channel`: Channel in the snapshot's pin numbering mode
+/// * `direction`: The direction the channel was set up with
+/// * `active_low`: Whether the channel is active-low, see `GPIO::set_active_low`
+/// * `label`: The label of the channel, see `GPIO::set_label`
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelSnapshot {
+    pub channel: Channel,
+    pub direction: Direction,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub active_low: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
+}
+
+/// A snapshot of the pin configuration of a `GPIO` object.
+///
+/// A snapshot is created with `GPIO::export_config` and can be handed to
+/// `GPIO::import_config` in a later process to resume control of the pins
+/// without re-exporting or re-driving them. With the `serde` feature enabled
+/// the snapshot can be serialized, e.g. to a file that survives a restart.
+///
+/// The fields are:
+/// * `mode`: The pin numbering mode, if one was set
+/// * `channels`: The configured channels
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigSnapshot {
+    pub mode: Option<Mode>,
+    pub channels: Vec<ChannelSnapshot>,
+}
+
+/// The result of `GPIO::self_test`, describing the detected environment.
+///
+/// The report implements `Display`, so it can be printed and pasted into a bug report.
+///
+/// The fields are:
+/// * `model`: The detected Jetson model
+/// * `write_access`: `Ok` if the GPIO sysfs interface is writable, otherwise the reason it is not
+/// * `gpio_chips`: The resolved GPIO chips as (sysfs name, base, ngpio)
+/// * `unresolved_gpio_pins`: Board pins whose GPIO chip could not be resolved
+/// * `unresolved_pwm_pins`: PWM-capable board pins whose PWM chip could not be resolved
+/// * `modes`: The pin numbering modes that have channel data
+#[derive(Clone)]
+pub struct SelfTestReport {
+    pub model: String,
+    pub write_access: Result<(), String>,
+    pub gpio_chips: Vec<(String, u32, u32)>,
+    pub unresolved_gpio_pins: Vec<u32>,
+    pub unresolved_pwm_pins: Vec<u32>,
+    pub modes: Vec<Mode>,
+}
 
-    write_value(ch_info, value_str.to_string());
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Model: {}", self.model)?;
+        match &self.write_access {
+            Ok(()) => writeln!(f, "Write access: yes")?,
+            Err(e) => writeln!(f, "Write access: no ({})", e)?,
+        }
+        writeln!(f, "GPIO chips:")?;
+        for (name, base, ngpio) in self.gpio_chips.iter() {
+            writeln!(f, "  {}: base {}, ngpio {}", name, base, ngpio)?;
+        }
+        writeln!(f, "Unresolved GPIO pins: {:?}", self.unresolved_gpio_pins)?;
+        writeln!(f, "Unresolved PWM pins: {:?}", self.unresolved_pwm_pins)?;
+        let modes: Vec<&str> = self.modes.iter().map(|m| m.to_str()).collect();
+        write!(f, "Modes: {}", modes.join(", "))
+    }
 }
 
 /// A public struct that holds state information about the GPIO pins.
@@ -185,380 +765,3565 @@ fn output_one(ch_info: ChannelInfo, value: Level) {
 /// * `model` - The model of the Jetson board
 /// * `jetson_info` - A `JetsonInfo` struct that holds information about the Jetson board
 ///
+/// `GPIO` is not `Clone`. It owns the exported channels, the requested lines and
+/// the edge detection threads, so two copies would clean up and reconfigure the
+/// same pins behind each other's back. To hand one `GPIO` object to several parts
+/// of a program or to worker threads, turn it into a `SharedGpio` with `shared`:
+/// its clones all refer to the same object and its channel state.
+///
 /// # Example
 ///
 /// ```rust
 /// use jetson_gpio::GPIO;
 ///
 /// let gpio = GPIO::new();
+///
+/// // a handle for a worker closure
+/// let shared = gpio.shared();
+/// let worker = shared.clone();
 /// ```
 pub struct GPIO {
     pub model: String,
     pub jetson_info: JetsonInfo,
-    channel_data_by_mode: HashMap<Mode, HashMap<u32, ChannelInfo>>,
+    channel_data_by_mode: ChannelDataByMode,
+    gpio_chips: Vec<GpioChip>,
+    pin_aliases: Vec<PinAliases>,
 
     // # Dictionary objects used as lookup tables for pin to linux gpio mapping
-    channel_data: HashMap<u32, ChannelInfo>,
+    channel_data: HashMap<Channel, ChannelInfo>,
 
     gpio_warnings: bool,
+    warning_handler: WarningHandler,
+    // channels already reported as in use by another process
+    warned_in_use: HashSet<Channel>,
     gpio_mode: Option<Mode>,
-    channel_configuration: HashMap<u32, Direction>,
+    channel_configuration: HashMap<Channel, Direction>,
+    // re-export channels on setup even if sysfs already has the requested direction
+    reexport_on_setup: bool,
+
+    poll_interval: Duration,
+    export_timeout: Duration,
+    export_retry: RetryPolicy,
+    watchdog: Option<Watchdog>,
+
+    fd_caching: bool,
+    value_files: HashMap<Channel, File>,
+
+    pwm_frequency_limit: PwmFrequencyLimit,
+
+    events: HashMap<Channel, EventDetector>,
+
+    backend: Backend,
+    // lines requested from the character devices, by channel
+    lines: HashMap<Channel, RequestedLine>,
+    mock_pins: Arc<MockPins>,
+
+    // channels whose logical level is the inverse of the electrical level
+    active_low: HashSet<Channel>,
+    // names given to channels by the program, see `set_label`
+    labels: HashMap<Channel, String>,
+    // outputs that only drive LOW and float for HIGH, see `Drive::OPEN_DRAIN`
+    open_drain: HashSet<Channel>,
+    // inputs debounced in software by `input`, with the time their level must be held and
+    // the poll interval to sample them with, if it differs from `poll_interval`
+    debounce: HashMap<Channel, (Duration, Option<Duration>)>,
+    // the logical level last written to each output channel
+    last_outputs: Arc<Mutex<HashMap<Channel, Level>>>,
 }
 
 impl GPIO {
-    /// Creates a new `GPIO` object.
+    /// Creates a new `GPIO` object that uses the sysfs backend.
     ///
     /// Calling this function will automatically populate the `model` and `jetson_info` fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board cannot be identified, see `try_new`.
     pub fn new() -> Self {
-        let (model, jetson_info, channel_data_by_mode) = get_data();
-
-        GPIO {
-            model,
-            jetson_info,
-            channel_data_by_mode,
-
-            channel_data: HashMap::new(),
+        GPIO::try_new().unwrap()
+    }
 
-            gpio_warnings: true,
-            gpio_mode: None,
-            channel_configuration: HashMap::new(),
-        }
+    /// Creates a new `GPIO` object that uses the sysfs backend.
+    ///
+    /// Returns an error instead of panicking if the Jetson model cannot be determined
+    /// or the GPIO chips cannot be read, e.g. when not running on a Jetson board.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::GPIO;
+    ///
+    /// match GPIO::try_new() {
+    ///     Ok(gpio) => println!("Running on {}", gpio.model),
+    ///     Err(e) => eprintln!("Not a Jetson board: {}", e),
+    /// }
+    /// ```
+    pub fn try_new() -> Result<Self, Error> {
+        GPIO::try_new_with_backend(Backend::SYSFS)
     }
 
-    /// Enable or disable warnings during setup and cleanup.
+    /// Creates a new `GPIO` object that uses the given kernel interface.
     ///
     /// # Arguments
     ///
-    /// * `warnings` - `true` to enable warnings, `false` to disable warnings
-    pub fn setwarnings(&mut self, warnings: bool) {
-        self.gpio_warnings = warnings;
+    /// * `backend` - `Backend::SYSFS`, `Backend::CHARDEV` or `Backend::MOCK`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board cannot be identified, see `try_new_with_backend`.
+    pub fn new_with_backend(backend: Backend) -> Self {
+        GPIO::try_new_with_backend(backend).unwrap()
     }
 
-    /// Sets the pin mumbering mode.
-    ///
-    /// Possible mode values are
-    /// * `Mode::BOARD`
-    /// * `Mode::BCM`
-    /// * `Mode::TEGRA_SOC`
-    /// * `Mode::CVM`
+    /// Creates a new `GPIO` object that uses the given kernel interface, returning an
+    /// error if the board cannot be identified.
     ///
     /// # Arguments
     ///
-    /// * `mode` - The pin numbering mode to use
-    pub fn setmode(&mut self, mode: Mode) -> Result<(), Error> {
-        // check if a different mode has been set already
-        if let Some(current_mode) = self.gpio_mode {
-            if current_mode != mode {
-                return Err(Error::msg("A different mode has already been set!"));
-            }
-        }
+    /// * `backend` - `Backend::SYSFS`, `Backend::CHARDEV` or `Backend::MOCK`
+    pub fn try_new_with_backend(backend: Backend) -> Result<Self, Error> {
+        let backend = effective_backend(backend);
+        let data = match backend {
+            Backend::MOCK => get_mock_data(None, &print_warning)?,
+            _ => get_data(&print_warning)?,
+        };
 
-        // check if mode parameter is valid
-        if !mode.is_valid() {
-            return Err(Error::msg("An invalid mode was passed to setmode!"));
-        }
-
-        self.channel_data = self.channel_data_by_mode.get(&mode).unwrap().clone();
-        self.gpio_mode = Some(mode);
+        Ok(GPIO::from_data(data, backend))
+    }
 
-        Ok(())
+    /// Creates a new `GPIO` object that finds the kernel interfaces at the given locations.
+    ///
+    /// The locations apply to the whole process, see `GpioPaths`.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The locations of sysfs, the device tree and the character devices
+    /// * `backend` - `Backend::SYSFS`, `Backend::CHARDEV` or `Backend::MOCK`
+    pub fn new_with_paths(paths: GpioPaths, backend: Backend) -> Result<Self, Error> {
+        set_paths(paths);
+        GPIO::try_new_with_backend(backend)
     }
 
-    /// Returns the currently set pin numbering mode as an `Option<String>`.
-    pub fn getmode(&self) -> Option<String> {
-        match self.gpio_mode {
+    /// Creates a new `GPIO` object for the given Jetson model, using the sysfs backend.
+    ///
+    /// Unlike `new`, the model is not detected from the device tree, which is
+    /// useful in containers or to force a board. The GPIO chips of the model are
+    /// still looked up in sysfs. Returns an error listing the valid models if
+    /// `model` is not one of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model name, e.g. `"JETSON_NANO"` or `"JETSON_ORIN_NX"`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new_for_model("JETSON_NX").unwrap();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// ```
+    pub fn new_for_model(model: &str) -> Result<Self, Error> {
+        GPIO::builder().model_override(model).build()
+    }
+
+    /// Returns a `GpioBuilder` to create a `GPIO` object with its warnings,
+    /// pin numbering mode, backend and model set up front.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::builder().mode(Mode::BOARD).warnings(false).build().unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    /// ```
+    pub fn builder() -> GpioBuilder {
+        GpioBuilder::new()
+    }
+
+    pub(crate) fn from_data(data: BoardData, backend: Backend) -> Self {
+        let (model, jetson_info, channel_data_by_mode, gpio_chips, pin_aliases) = data;
+
+        GPIO {
+            model,
+            jetson_info,
+            channel_data_by_mode,
+            gpio_chips,
+            pin_aliases,
+
+            channel_data: HashMap::new(),
+
+            gpio_warnings: true,
+            warning_handler: Arc::new(print_warning),
+            warned_in_use: HashSet::new(),
+            gpio_mode: None,
+            channel_configuration: HashMap::new(),
+            reexport_on_setup: false,
+
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            export_timeout: DEFAULT_EXPORT_TIMEOUT,
+            export_retry: DEFAULT_EXPORT_RETRY,
+            watchdog: None,
+
+            fd_caching: false,
+            value_files: HashMap::new(),
+
+            pwm_frequency_limit: PwmFrequencyLimit::REJECT,
+
+            events: HashMap::new(),
+
+            backend,
+            lines: HashMap::new(),
+            mock_pins: Arc::default(),
+            active_low: HashSet::new(),
+            labels: HashMap::new(),
+            open_drain: HashSet::new(),
+            debounce: HashMap::new(),
+            last_outputs: Arc::default(),
+        }
+    }
+
+    /// Moves the `GPIO` object into a `SharedGpio` handle that can be cloned and used from several threads.
+    ///
+    /// See `SharedGpio` for which operations can run concurrently.
+    pub fn shared(self) -> SharedGpio {
+        SharedGpio::new(self)
+    }
+
+    /// Enable or disable warnings during setup and cleanup.
+    ///
+    /// # Arguments
+    ///
+    /// * `warnings` - `true` to enable warnings, `false` to disable warnings
+    pub fn setwarnings(&mut self, warnings: bool) -> &mut Self {
+        self.gpio_warnings = warnings;
+        self
+    }
+
+    /// Sets the function that receives warnings, instead of printing them to stdout.
+    ///
+    /// The handler is not called while warnings are disabled with `setwarnings(false)`.
+    /// To also receive the warnings printed while the board is detected, set the
+    /// handler with `GpioBuilder::warning_handler` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The function called with the message of every warning
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.set_warning_handler(|message| eprintln!("jetson_gpio: {}", message));
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// ```
+    pub fn set_warning_handler<F: Fn(&str) + Send + Sync + 'static>(&mut self, handler: F) -> &mut Self {
+        self.warning_handler = Arc::new(handler);
+        self
+    }
+
+    fn warn(&self, message: &str) {
+        if self.gpio_warnings {
+            (self.warning_handler)(message);
+        }
+    }
+
+    // Warns that a channel is used outside of this object, once per channel, so
+    // programs that set up the same channels repeatedly don't flood their logs.
+    fn warn_in_use(&mut self, channel: &Channel) {
+        if self.gpio_warnings && self.warned_in_use.insert(channel.clone()) {
+            self.warn(&format!(
+                "Channel {} is already in use, continuing anyway. Use GPIO.setwarnings(False) to disable warnings",
+                channel
+            ));
+        }
+    }
+
+    /// Sets whether `setup` re-exports channels that sysfs already reports with the requested direction.
+    ///
+    /// By default, `setup` leaves a channel that is already exported with the
+    /// requested direction as it is, because writing the direction again
+    /// briefly glitches the pin (an output is driven low). Enable this to always
+    /// clean up and re-export channels, as earlier versions did. Only the sysfs
+    /// backend is affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `reexport` - `true` to always re-export channels on `setup`
+    pub fn set_reexport_on_setup(&mut self, reexport: bool) -> &mut Self {
+        self.reexport_on_setup = reexport;
+        self
+    }
+
+    // Whether sysfs already has the channel exported with the given direction,
+    // so that setting it up again can skip the export and the direction write.
+    fn keeps_sysfs_direction(&self, ch_info: &ChannelInfo, direction: Direction) -> bool {
+        self.backend == Backend::SYSFS
+            && !self.reexport_on_setup
+            && sysfs_channel_configuration(ch_info.clone()) == Some(direction)
+    }
+
+    /// Sets the interval between reads used by the polling helpers such as `wait_for_stable`.
+    ///
+    /// A shorter interval reacts faster to changes at the cost of CPU time. The
+    /// default is 1 ms. The `_with_interval` variants of the helpers override it
+    /// for a single call or channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The time to sleep between two reads of an input
+    pub fn set_poll_interval(&mut self, interval: Duration) -> &mut Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Sets how long `setup` and `pwm` wait for a channel exported through sysfs to become usable.
+    ///
+    /// If the files of the channel do not appear in time, e.g. because another
+    /// subsystem owns the pin, `setup` or `pwm` returns an error. The default is 1 s.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum time to wait after exporting a channel
+    pub fn set_export_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.export_timeout = timeout;
+        self
+    }
+
+    /// Sets how often a failed sysfs export or unexport of a channel is retried.
+    ///
+    /// Once the retries are exhausted, `setup` and `cleanup` return a
+    /// `GpioError::EXPORT_FAILED` error. The default is 3 retries with a backoff of 100 ms.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of retries and the delay between them, see `RetryPolicy`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, RetryPolicy};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.set_export_retry(RetryPolicy { retries: 5, backoff: Duration::from_millis(200) });
+    /// ```
+    pub fn set_export_retry(&mut self, retry: RetryPolicy) -> &mut Self {
+        self.export_retry = retry;
+        self
+    }
+
+    /// Sets how PWM frequencies above the maximum of the PWM controller are handled.
+    ///
+    /// By default such frequencies are rejected with an error. The setting
+    /// applies to PWM objects created after the call.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - `PwmFrequencyLimit::REJECT` or `PwmFrequencyLimit::CLAMP`
+    pub fn set_pwm_frequency_limit(&mut self, limit: PwmFrequencyLimit) -> &mut Self {
+        self.pwm_frequency_limit = limit;
+        self
+    }
+
+    /// Enable or disable caching of the open `value` file of each channel.
+    ///
+    /// When enabled, the `value` file of a channel is opened once when the
+    /// channel is set up and reused by `input` and `output`, which avoids an
+    /// `open` syscall on every access. When disabled (the default), the file is
+    /// reopened for every read and write. Enabling caching also opens the
+    /// files of channels that are already set up. The character device
+    /// backend always keeps its lines open, so the setting has no effect there.
+    ///
+    /// # Arguments
+    ///
+    /// * `caching` - `true` to keep the files open, `false` to reopen them on every access
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    pub fn set_fd_caching(&mut self, caching: bool) -> Result<&mut Self, Error> {
+        self.fd_caching = caching;
+        self.value_files.clear();
+
+        if caching {
+            for channel in self.channel_configuration.clone().keys() {
+                let ch_info = self.channel_to_info(channel, true, false)?;
+                self.cache_value_file(&ch_info)?;
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Sets the pin mumbering mode.
+    ///
+    /// Possible mode values are
+    /// * `Mode::BOARD`
+    /// * `Mode::BCM`
+    /// * `Mode::TEGRA_SOC`
+    /// * `Mode::CVM`
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The pin numbering mode to use
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    ///
+    /// The mode can only be changed while no channels are set up. Clean up the
+    /// channels of the current mode first, with `cleanup` or `cleanup_channels_only`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setwarnings(false)
+    ///     .setmode(Mode::BOARD).unwrap()
+    ///     .setup(vec![7], Direction::OUT, None, None).unwrap();
+    /// ```
+    pub fn setmode(&mut self, mode: Mode) -> Result<&mut Self, Error> {
+        // a different mode can only be set once the channels of the current one are cleaned up
+        if let Some(current_mode) = self.gpio_mode {
+            if current_mode != mode && !self.channel_configuration.is_empty() {
+                return Err(Error::msg(format!(
+                    "A different mode has already been set: {} (requested {}). Clean up its channels first",
+                    current_mode, mode
+                )));
+            }
+        }
+
+        // check if mode parameter is valid
+        if !mode.is_valid() {
+            return Err(Error::msg("An invalid mode was passed to setmode!"));
+        }
+
+        if self.gpio_mode != Some(mode) {
+            // channels are named differently in the new mode
+            self.active_low.clear();
+            self.labels.clear();
+        }
+        self.channel_data = self.channel_data_by_mode.get(&mode).unwrap().clone();
+        self.gpio_mode = Some(mode);
+
+        Ok(self)
+    }
+
+    /// Returns the currently set pin numbering mode as an `Option<String>`.
+    pub fn getmode(&self) -> Option<String> {
+        match self.gpio_mode {
             Some(mode) => Some(String::from(mode.to_str())),
             None => None,
         }
     }
 
-    fn validate_mode_set(&self) -> Result<(), Error> {
-        match self.gpio_mode {
-            Some(_) => Ok(()),
-            None => Err(Error::msg("Please set pin numbering mode using GPIO.setmode(Mode::BOARD), GPIO.setmode(Mode::BCM), GPIO.setmode(Mode::TEGRA_SOC) or GPIO.setmode(Mode::CVM)")),
+    /// Returns the currently set pin numbering mode, or `None` if it has not been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// if let Some(Mode::BOARD) = gpio.mode() {
+    ///     println!("Using physical pin numbers");
+    /// }
+    /// ```
+    pub fn mode(&self) -> Option<Mode> {
+        self.gpio_mode
+    }
+
+    /// Returns the sorted list of valid channels for the given pin numbering mode.
+    ///
+    /// The currently set mode is not changed, so this can be used to look up
+    /// the channels of another mode, e.g. the BCM channels while in BOARD mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The pin numbering mode to list the channels of
+    pub fn mode_channels(&self, mode: Mode) -> Vec<Channel> {
+        let mut channels: Vec<Channel> = match self.channel_data_by_mode.get(&mode) {
+            Some(channel_data) => channel_data.keys().cloned().collect(),
+            None => Vec::new(),
+        };
+        channels.sort();
+        channels
+    }
+
+    /// Returns the GPIO pins of the header of the detected board, sorted by board number.
+    ///
+    /// Each entry lists the identifiers of the pin in every pin numbering mode and
+    /// whether it is PWM capable. The pin numbering mode does not need to be set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::GPIO;
+    ///
+    /// let gpio = GPIO::new();
+    /// for pin in gpio.pin_table() {
+    ///     let pwm = if pin.pwm_capable { " (PWM)" } else { "" };
+    ///     println!("{:>2} {:>8} {}{}", pin.board, pin.cvm, pin.tegra_soc, pwm);
+    /// }
+    /// ```
+    pub fn pin_table(&self) -> Vec<PinMapEntry> {
+        let board_data = self.channel_data_by_mode.get(&Mode::BOARD);
+        let mut table: Vec<PinMapEntry> = self
+            .pin_aliases
+            .iter()
+            .map(|aliases| PinMapEntry {
+                board: aliases.board,
+                bcm: aliases.bcm,
+                cvm: aliases.cvm.clone(),
+                tegra_soc: aliases.tegra_soc.clone(),
+                pwm_capable: board_data
+                    .and_then(|board_data| board_data.get(&Channel::Number(aliases.board)))
+                    .is_some_and(|ch_info| ch_info.pwm_id.is_some()),
+            })
+            .collect();
+        table.sort_by_key(|entry| entry.board);
+        table
+    }
+
+    /// Returns the sorted list of valid channels for the current pin numbering mode.
+    ///
+    /// Returns an error if no mode has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// for channel in gpio.available_channels().unwrap() {
+    ///     println!("{}", channel);
+    /// }
+    /// ```
+    pub fn available_channels(&self) -> Result<Vec<Channel>, Error> {
+        self.validate_mode_set()?;
+
+        let mut channels: Vec<Channel> = self.channel_data.keys().cloned().collect();
+        channels.sort();
+        Ok(channels)
+    }
+
+    /// Returns the sorted list of channels of the current pin numbering mode that can be
+    /// used with `pwm`.
+    ///
+    /// Only channels whose PWM controller was found in sysfs are listed. Returns
+    /// an error if no mode has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// match gpio.pwm_channels().unwrap().first() {
+    ///     Some(channel) => println!("Using channel {} for PWM", channel),
+    ///     None => println!("No PWM channel is available"),
+    /// }
+    /// ```
+    pub fn pwm_channels(&self) -> Result<Vec<Channel>, Error> {
+        let mut channels = self.available_channels()?;
+        channels.retain(|channel| self.channel_data[channel].pwm_chip_dir.is_some());
+        Ok(channels)
+    }
+
+    /// Returns the direction and level of every channel of the current pin numbering mode,
+    /// sorted by channel.
+    ///
+    /// Channels set up by this object report their configured direction and
+    /// the level returned by `input`. Other channels report the direction and
+    /// level found in sysfs, so channels exported by other processes are
+    /// included, or `Direction::UNKNOWN` and `None` if the channel is not
+    /// exported. The character device and mock backends only know the channels
+    /// set up by this object. The level is `None` for PWM channels and channels
+    /// whose value cannot be read. Returns an error if no mode has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// for (channel, direction, level) in gpio.dump_state().unwrap() {
+    ///     match level {
+    ///         Some(level) => println!("{}: {} {}", channel, direction, level),
+    ///         None => println!("{}: {}", channel, direction),
+    ///     }
+    /// }
+    /// ```
+    pub fn dump_state(&self) -> Result<Vec<(Channel, Direction, Option<Level>)>, Error> {
+        let mut state = Vec::new();
+        for channel in self.available_channels()? {
+            let ch_info = self.channel_data[&channel].clone();
+            let (direction, level) = match self.app_channel_configuration(ch_info.clone()) {
+                Some(Direction::HARD_PWM) => (Direction::HARD_PWM, None),
+                Some(direction) => (direction, self.input(channel.clone()).ok()),
+                None if self.backend == Backend::SYSFS && !ch_info.gpio_chip_dir.is_empty() => {
+                    match sysfs_channel_configuration(ch_info.clone()) {
+                        Some(Direction::HARD_PWM) => (Direction::HARD_PWM, None),
+                        Some(direction) => (direction, read_sysfs_level(&ch_info)),
+                        None => (Direction::UNKNOWN, None),
+                    }
+                }
+                None => (Direction::UNKNOWN, None),
+            };
+            state.push((channel, direction, level));
+        }
+
+        Ok(state)
+    }
+
+    /// Returns the information of a channel in the current pin numbering mode.
+    ///
+    /// Returns `None` if no mode has been set or the channel does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to look up.
+    pub fn channel_info<C: Into<Channel>>(&self, channel: C) -> Option<ChannelInfo> {
+        self.gpio_mode?;
+        self.channel_data.get(&channel.into()).cloned()
+    }
+
+    /// Returns the sorted list of channels that are currently exported as PWM in sysfs.
+    ///
+    /// Only PWM-capable channels of the current pin numbering mode are scanned.
+    /// A channel is reported regardless of which process exported it, so this
+    /// can be used to avoid conflicting with a PWM that is already running.
+    /// The list is always empty with the mock backend.
+    pub fn active_pwm_channels(&self) -> Vec<Channel> {
+        if self.backend == Backend::MOCK {
+            return Vec::new();
+        }
+
+        let mut channels: Vec<Channel> = self
+            .channel_data
+            .values()
+            .filter(|ch_info| ch_info.pwm_chip_dir.is_some())
+            .filter(|ch_info| sysfs_channel_configuration((*ch_info).clone()) == Some(Direction::HARD_PWM))
+            .map(|ch_info| ch_info.channel.clone())
+            .collect();
+        channels.sort();
+        channels
+    }
+
+    /// Returns the sysfs directory used for the channel, e.g. `/sys/class/gpio/PQ.06`.
+    ///
+    /// The directory only exists while the channel is exported.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to look up.
+    pub fn sysfs_path<C: Into<Channel>>(&self, channel: C) -> Result<PathBuf, Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        Ok(Path::new(&sysfs_gpio_root()).join(ch_info.global_gpio_name))
+    }
+
+    /// Returns every identifier of the pin behind a channel of the current mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to look up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let aliases = gpio.pin_aliases(15).unwrap();
+    /// if let Some(bcm) = aliases.bcm {
+    ///     println!("BOARD 15 is BCM {} ({})", bcm, aliases.tegra_soc);
+    /// }
+    /// ```
+    pub fn pin_aliases<C: Into<Channel>>(&self, channel: C) -> Result<PinAliases, Error> {
+        let channel = channel.into();
+        self.channel_to_info(&channel, false, false)?;
+
+        match self.gpio_mode.and_then(|mode| self.find_pin_aliases(mode, &channel)) {
+            Some(aliases) => Ok(aliases.clone()),
+            None => Err(Error::new(GpioError::INVALID_CHANNEL(channel.clone()))),
+        }
+    }
+
+    /// Translates a channel from one pin numbering mode to another.
+    ///
+    /// The pin numbering mode of this object does not need to be set and is not
+    /// changed. Returns an error if `channel` is not a pin in the `from` mode, or
+    /// if the pin has no number in the `to` mode (not every pin has a BCM number).
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel in the `from` mode.
+    /// * `from` - The pin numbering mode of `channel`.
+    /// * `to` - The pin numbering mode to translate to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let gpio = GPIO::new();
+    /// let bcm = gpio.translate(15, Mode::BOARD, Mode::BCM).unwrap();
+    /// println!("BOARD 15 is BCM {}", bcm);
+    /// ```
+    pub fn translate<C: Into<Channel>>(&self, channel: C, from: Mode, to: Mode) -> Result<Channel, Error> {
+        let channel = channel.into();
+        let aliases = self
+            .find_pin_aliases(from, &channel)
+            .ok_or_else(|| Error::new(GpioError::INVALID_CHANNEL(channel.clone())))?;
+
+        match to {
+            Mode::BOARD => Ok(Channel::Number(aliases.board)),
+            Mode::BCM => match aliases.bcm {
+                Some(bcm) => Ok(Channel::Number(bcm)),
+                None => Err(Error::msg(format!("Channel {} has no {} number", channel, to))),
+            },
+            Mode::TEGRA_SOC => Ok(Channel::Name(aliases.tegra_soc.clone())),
+            Mode::CVM => Ok(Channel::Name(aliases.cvm.clone())),
+        }
+    }
+
+    fn find_pin_aliases(&self, mode: Mode, channel: &Channel) -> Option<&PinAliases> {
+        self.pin_aliases.iter().find(|aliases| match (mode, channel) {
+            (Mode::BOARD, Channel::Number(number)) => aliases.board == *number,
+            (Mode::BCM, Channel::Number(number)) => aliases.bcm == Some(*number),
+            (Mode::TEGRA_SOC, Channel::Name(name)) => aliases.tegra_soc == *name,
+            (Mode::CVM, Channel::Name(name)) => aliases.cvm == *name,
+            _ => false,
+        })
+    }
+
+    fn validate_mode_set(&self) -> Result<(), Error> {
+        match self.gpio_mode {
+            Some(_) => Ok(()),
+            None => Err(Error::new(GpioError::MODE_NOT_SET)),
+        }
+    }
+
+    fn channel_to_info_lookup(
+        &self,
+        channel: &Channel,
+        need_gpio: bool,
+        need_pwm: bool,
+    ) -> Result<ChannelInfo, Error> {
+        if !self.channel_data.contains_key(channel) {
+            return Err(Error::new(GpioError::INVALID_CHANNEL(channel.clone())));
+        }
+
+        let ch_info = self.channel_data.get(channel).unwrap().clone();
+
+        if need_gpio && ch_info.gpio_chip_dir == "" {
+            return Err(Error::new(GpioError::NOT_A_GPIO(channel.clone())));
+        }
+
+        if need_pwm && ch_info.pwm_chip_dir.is_none() {
+            return Err(Error::new(GpioError::NOT_A_PWM(channel.clone())));
+        }
+
+        Ok(ch_info)
+    }
+
+    fn channel_to_info(
+        &self,
+        channel: &Channel,
+        need_gpio: bool,
+        need_pwm: bool,
+    ) -> Result<ChannelInfo, Error> {
+        self.validate_mode_set()?;
+        self.channel_to_info_lookup(channel, need_gpio, need_pwm)
+    }
+
+    fn channels_to_infos(
+        &self,
+        channels: Vec<Channel>,
+        need_gpio: bool,
+        need_pwm: bool,
+    ) -> Result<Vec<ChannelInfo>, Error> {
+        self.validate_mode_set()?;
+        let mut ret: Vec<ChannelInfo> = Vec::new();
+        for channel in channels {
+            ret.push(self.channel_to_info_lookup(&channel, need_gpio, need_pwm)?);
+        }
+
+        Ok(ret)
+    }
+
+    fn app_channel_configuration(&self, ch_info: ChannelInfo) -> Option<Direction> {
+        // """Return the current configuration of a channel as requested by this
+        // module in this process. Any of IN, OUT, or None may be returned."""
+
+        match self.channel_configuration.get(&ch_info.channel) {
+            Some(direction) => Some(direction.clone()),
+            None => None,
+        }
+    }
+
+    fn cache_value_file(&mut self, ch_info: &ChannelInfo) -> Result<(), Error> {
+        if self.fd_caching && self.backend == Backend::SYSFS {
+            let f_value = open_value_file(ch_info)?;
+            self.value_files.insert(ch_info.channel.clone(), f_value);
+        }
+
+        Ok(())
+    }
+
+    fn requested_line(&self, ch_info: &ChannelInfo) -> Result<&RequestedLine, Error> {
+        match self.lines.get(&ch_info.channel) {
+            Some(line) => Ok(line),
+            None => Err(Error::msg(format!("Channel {} has not been requested", ch_info.channel))),
+        }
+    }
+
+    fn pin_io(&self, ch_info: &ChannelInfo) -> Result<PinIo, Error> {
+        match self.backend {
+            Backend::SYSFS => Ok(PinIo::Sysfs(open_value_file(ch_info)?)),
+            Backend::CHARDEV => {
+                let line = self.requested_line(ch_info)?;
+                Ok(PinIo::Line(line.file.try_clone()?, line.index))
+            }
+            Backend::MOCK => Ok(PinIo::Mock(self.mock_pins.clone())),
+        }
+    }
+
+    // Converts between logical and electrical levels of active-low channels.
+    fn electrical_level(&self, channel: &Channel, value: Level) -> Level {
+        match self.active_low.contains(channel) {
+            true => value.inverted(),
+            false => value,
+        }
+    }
+
+    fn read_channel(&self, ch_info: ChannelInfo) -> Result<String, Error> {
+        let active_low = self.active_low.contains(&ch_info.channel);
+        let value = self.read_channel_value(ch_info)?;
+
+        match (active_low, value.as_str()) {
+            (true, "0") => Ok(String::from("1")),
+            (true, "1") => Ok(String::from("0")),
+            _ => Ok(value),
+        }
+    }
+
+    fn read_channel_value(&self, ch_info: ChannelInfo) -> Result<String, Error> {
+        match self.backend {
+            Backend::CHARDEV => {
+                let line = self.requested_line(&ch_info)?;
+                return Ok(get_line_value(&line.file, line.index)?.sysfs_str().to_string());
+            }
+            Backend::MOCK => return Ok(self.mock_pins.get(&ch_info)?.sysfs_str().to_string()),
+            Backend::SYSFS => {}
+        }
+
+        match self.value_files.get(&ch_info.channel) {
+            Some(f_value) => Ok(read_value_file(f_value)?),
+            None => read_value(ch_info),
+        }
+    }
+
+    fn write_channel(&self, ch_info: ChannelInfo, value: Level) -> Result<(), Error> {
+        let level = self.electrical_level(&ch_info.channel, value.clone());
+        if self.open_drain.contains(&ch_info.channel) {
+            self.write_open_drain(&ch_info, level)?;
+            self.record_output(&ch_info.channel, value);
+            return Ok(());
+        }
+
+        match self.backend {
+            Backend::CHARDEV => {
+                let line = self.requested_line(&ch_info)?;
+                set_line_value(&line.file, line.index, level)?;
+            }
+            Backend::MOCK => self.mock_pins.set(&ch_info, Direction::OUT, level)?,
+            Backend::SYSFS => match self.value_files.get(&ch_info.channel) {
+                Some(f_value) => write_value_file(f_value, level.sysfs_str().to_string())?,
+                None => output_one(ch_info.clone(), level)?,
+            },
+        }
+        self.record_output(&ch_info.channel, value);
+
+        Ok(())
+    }
+
+    // Writes the electrical level of an open-drain output. The character device backend
+    // configures the line as open-drain, the other backends emulate it.
+    fn write_open_drain(&self, ch_info: &ChannelInfo, value: Level) -> Result<(), Error> {
+        match self.backend {
+            Backend::SYSFS => write_open_drain(ch_info.clone(), value)?,
+            Backend::CHARDEV => {
+                let line = self.requested_line(ch_info)?;
+                set_line_value(&line.file, line.index, value)?;
+            }
+            // a floating line reads HIGH, as if it had a pull-up resistor
+            Backend::MOCK => match value {
+                Level::HIGH => self.mock_pins.export(ch_info, Direction::IN, Pull::UP, None),
+                Level::LOW => self.mock_pins.export(ch_info, Direction::OUT, Pull::OFF, Some(Level::LOW)),
+            },
+        }
+
+        Ok(())
+    }
+
+    // Remembers the logical level written to an output channel for `last_output`.
+    fn record_output(&self, channel: &Channel, value: Level) {
+        self.last_outputs.lock().unwrap().insert(channel.clone(), value);
+    }
+
+    fn cleanup_one(&mut self, ch_info: ChannelInfo) -> Result<(), Error> {
+        self.value_files.remove(&ch_info.channel);
+        self.open_drain.remove(&ch_info.channel);
+        self.debounce.remove(&ch_info.channel);
+        self.last_outputs.lock().unwrap().remove(&ch_info.channel);
+
+        // the watchdog must not drive a pin that is no longer set up
+        if self.watchdog.as_ref().is_some_and(|w| w.watches(&ch_info.channel)) {
+            self.warn(&format!(
+                "Channel {} is watched by the watchdog, disabling the watchdog",
+                ch_info.channel
+            ));
+            self.watchdog = None;
+        }
+
+        match self.channel_configuration.get(&ch_info.channel) {
+            Some(direction) => {
+                if direction == &Direction::HARD_PWM {
+                    disable_pwm(&ch_info)?;
+                    unexport_pwm(&ch_info)?;
+                } else {
+                    // stop the event thread before the value file goes away
+                    self.events.remove(&ch_info.channel);
+                    match self.backend {
+                        Backend::SYSFS => unexport_gpio(ch_info.clone(), self.export_retry)?,
+                        Backend::CHARDEV => self.release_line(&ch_info)?,
+                        Backend::MOCK => self.mock_pins.unexport(&ch_info),
+                    }
+                }
+            }
+            None => {}
+        }
+
+        self.channel_configuration.remove(&ch_info.channel);
+
+        Ok(())
+    }
+
+    // Requests the lines of output channels, one request per chip and drive, so that
+    // `output_atomic` can write the channels of a chip with a single ioctl.
+    // `initials` are electrical levels.
+    fn request_output_lines(&mut self, ch_infos: Vec<ChannelInfo>, initials: Vec<Option<Level>>) -> Result<(), Error> {
+        let mut chips: Vec<(Vec<ChannelInfo>, Vec<Option<Level>>)> = Vec::new();
+        let mut reused = Vec::new();
+        for (ch_info, initial) in ch_infos.into_iter().zip(initials) {
+            if self.lines.values().any(|line| line.file.index_of(&ch_info).is_some()) {
+                reused.push((ch_info, initial));
+                continue;
+            }
+            let same_request = |chip: &Vec<ChannelInfo>| {
+                chip[0].gpio_chip_dev == ch_info.gpio_chip_dev
+                    && self.drive(&chip[0].channel) == self.drive(&ch_info.channel)
+            };
+            match chips.iter_mut().find(|(chip, _)| same_request(chip)) {
+                Some((chip, chip_initials)) => {
+                    chip.push(ch_info);
+                    chip_initials.push(initial);
+                }
+                None => chips.push((vec![ch_info], vec![initial])),
+            }
+        }
+
+        // request every chip before keeping any line, so a failure releases them all
+        let mut requests = Vec::new();
+        for (chip, chip_initials) in chips {
+            let drive = self.drive(&chip[0].channel);
+            let file = Arc::new(request_lines(&chip, Direction::OUT, Pull::OFF, &chip_initials, drive)?);
+            requests.push((chip, file));
+        }
+        for (chip, file) in requests {
+            for (index, ch_info) in chip.into_iter().enumerate() {
+                let line = RequestedLine {
+                    file: file.clone(),
+                    index: index as u32,
+                };
+                self.lines.insert(ch_info.channel, line);
+            }
+        }
+        for (ch_info, initial) in reused {
+            let drive = self.drive(&ch_info.channel);
+            self.request_channel_line(&ch_info, Direction::OUT, Pull::OFF, initial, drive)?;
+        }
+
+        Ok(())
+    }
+
+    // Requests the line of a channel on its own. A line that is still held by the
+    // request of other channels, because it was released while they are in use,
+    // is configured in that request instead.
+    fn request_channel_line(
+        &mut self,
+        ch_info: &ChannelInfo,
+        direction: Direction,
+        pull: Pull,
+        initial: Option<Level>,
+        drive: Drive,
+    ) -> Result<(), Error> {
+        let held = self.lines.values().find_map(|line| {
+            line.file.index_of(ch_info).map(|index| RequestedLine {
+                file: line.file.clone(),
+                index,
+            })
+        });
+
+        let line = match held {
+            Some(line) => {
+                set_request_line(&line.file, ch_info, direction, pull, initial, drive)?;
+                line
+            }
+            None => RequestedLine {
+                file: Arc::new(request_lines(std::slice::from_ref(ch_info), direction, pull, &[initial], drive)?),
+                index: 0,
+            },
+        };
+        self.lines.insert(ch_info.channel.clone(), line);
+
+        Ok(())
+    }
+
+    fn drive(&self, channel: &Channel) -> Drive {
+        match self.open_drain.contains(channel) {
+            true => Drive::OPEN_DRAIN,
+            false => Drive::PUSH_PULL,
+        }
+    }
+
+    // Releases the line of a channel. The kernel only releases whole requests, so a
+    // line requested together with channels that are still in use becomes an input
+    // that no longer drives the pin, and is released with the last of them.
+    fn release_line(&mut self, ch_info: &ChannelInfo) -> Result<(), Error> {
+        let Some(line) = self.lines.remove(&ch_info.channel) else {
+            return Ok(());
+        };
+        if self.lines.values().any(|other| Arc::ptr_eq(&other.file, &line.file)) {
+            release_request_line(&line.file, ch_info)?;
+        }
+
+        Ok(())
+    }
+
+    fn reset_mode(&mut self) {
+        self.gpio_mode = None;
+        self.channel_data.clear();
+    }
+
+    fn setup_single_out(&mut self, ch_info: ChannelInfo, initial: Option<Level>) -> Result<(), Error> {
+        self.debounce.remove(&ch_info.channel);
+        match initial.clone() {
+            Some(initial) => self.record_output(&ch_info.channel, initial),
+            None => {
+                self.last_outputs.lock().unwrap().remove(&ch_info.channel);
+            }
+        }
+        let initial = initial.map(|initial| self.electrical_level(&ch_info.channel, initial));
+
+        match self.backend {
+            // an open-drain output without an initial level starts released
+            Backend::SYSFS if self.open_drain.contains(&ch_info.channel) => {
+                export_gpio(ch_info.clone(), self.export_timeout, self.export_retry)?;
+                write_open_drain(ch_info.clone(), initial.unwrap_or(Level::HIGH))?;
+                self.cache_value_file(&ch_info)?;
+            }
+            Backend::MOCK if self.open_drain.contains(&ch_info.channel) => {
+                self.write_open_drain(&ch_info, initial.unwrap_or(Level::HIGH))?;
+            }
+            Backend::SYSFS => {
+                if !self.keeps_sysfs_direction(&ch_info, Direction::OUT) {
+                    export_gpio(ch_info.clone(), self.export_timeout, self.export_retry)?;
+                    write_direction(ch_info.clone(), output_direction(initial.clone()).to_string())?;
+                } else if let Some(initial) = initial {
+                    output_one(ch_info.clone(), initial)?;
+                }
+
+                self.cache_value_file(&ch_info)?;
+            }
+            // requested together with the other channels of the chip by `setup_channels`
+            Backend::CHARDEV => {}
+            Backend::MOCK => self.mock_pins.export(&ch_info, Direction::OUT, Pull::OFF, initial),
+        }
+
+        self.channel_configuration
+            .insert(ch_info.channel.clone(), Direction::OUT);
+
+        Ok(())
+    }
+
+    fn setup_single_in(&mut self, ch_info: ChannelInfo, pull_up_down: Pull) -> Result<(), Error> {
+        self.debounce.remove(&ch_info.channel);
+        self.last_outputs.lock().unwrap().remove(&ch_info.channel);
+        match self.backend {
+            Backend::SYSFS => {
+                if pull_up_down != Pull::OFF {
+                    self.warn(&format!(
+                        "The sysfs interface cannot set the pull resistor of channel {}, ignoring pull_up_down. Use Backend::CHARDEV to configure it",
+                        ch_info.channel
+                    ));
+                }
+
+                if !self.keeps_sysfs_direction(&ch_info, Direction::IN) {
+                    export_gpio(ch_info.clone(), self.export_timeout, self.export_retry)?;
+                    write_direction(ch_info.clone(), "in".to_string())?;
+                }
+
+                self.cache_value_file(&ch_info)?;
+            }
+            Backend::CHARDEV => {
+                self.request_channel_line(&ch_info, Direction::IN, pull_up_down, None, Drive::PUSH_PULL)?;
+            }
+            Backend::MOCK => self.mock_pins.export(&ch_info, Direction::IN, pull_up_down, None),
+        }
+
+        self.channel_configuration
+            .insert(ch_info.channel.clone(), Direction::IN);
+
+        Ok(())
+    }
+
+    /// Setup a channel or list of channels with a direction and (optional) pull/up down control and (optional) initial value.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - The channels to setup, e.g. a `Vec` or an array. Numbers in `BOARD` and `BCM` mode, pin names in `TEGRA_SOC` and `CVM` mode.
+    /// * `direction` - `Level::IN` or `Level::OUT`
+    /// * `pull_up_down` - The pull resistor of an input channel, `Pull::UP`, `Pull::DOWN`, `Pull::OFF` or `None` for `Pull::OFF`.
+    /// * `initial` - An optional initial level for an output channel.
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode, Pull};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    /// gpio.setup([11, 13], Direction::IN, Pull::DOWN, None).unwrap();
+    /// ```
+    pub fn setup<C: Into<Channel>, I: IntoIterator<Item = C>, P: Into<Option<Pull>>>(
+        &mut self,
+        channels: I,
+        direction: Direction,
+        pull_up_down: P,
+        initial: Option<Level>,
+    ) -> Result<&mut Self, Error> {
+        let channels: Vec<Channel> = channels.into_iter().map(Into::into).collect();
+        let initials = vec![initial; channels.len()];
+        self.setup_channels(channels, direction, pull_up_down.into().unwrap_or(Pull::OFF), initials, Drive::PUSH_PULL)
+    }
+
+    /// Setup output channels, each with its own initial value.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of channels to setup.
+    /// * `direction` - Must be `Direction::OUT`
+    /// * `initials` - The initial level of each channel, in the order of `channels`.
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup_with_initials(vec![7, 11], Direction::OUT, vec![Level::HIGH, Level::LOW]).unwrap();
+    /// ```
+    pub fn setup_with_initials<C: Into<Channel>, I: IntoIterator<Item = C>>(
+        &mut self,
+        channels: I,
+        direction: Direction,
+        initials: Vec<Level>,
+    ) -> Result<&mut Self, Error> {
+        let channels: Vec<Channel> = channels.into_iter().map(Into::into).collect();
+        if initials.len() != channels.len() {
+            return Err(Error::msg(format!(
+                "{} initial values provided for {} channels",
+                initials.len(),
+                channels.len()
+            )));
+        }
+
+        let initials = initials.into_iter().map(Some).collect();
+        self.setup_channels(channels, direction, Pull::OFF, initials, Drive::PUSH_PULL)
+    }
+
+    /// Sets up output channels that are driven push-pull or open-drain, see `Drive`.
+    ///
+    /// `setup` always sets up push-pull outputs. An open-drain output drives its
+    /// pin LOW for `Level::LOW` and releases it for `Level::HIGH`, which is
+    /// needed for lines shared by several devices, such as a software I2C bus.
+    /// Without an initial level, an open-drain output starts released.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of channels to setup.
+    /// * `drive` - `Drive::PUSH_PULL` or `Drive::OPEN_DRAIN`
+    /// * `initial` - An optional initial level.
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Drive, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup_output_with_drive(vec![3, 5], Drive::OPEN_DRAIN, Some(Level::HIGH)).unwrap();
+    /// ```
+    pub fn setup_output_with_drive<C: Into<Channel>, I: IntoIterator<Item = C>>(
+        &mut self,
+        channels: I,
+        drive: Drive,
+        initial: Option<Level>,
+    ) -> Result<&mut Self, Error> {
+        let channels: Vec<Channel> = channels.into_iter().map(Into::into).collect();
+        let initials = vec![initial; channels.len()];
+        self.setup_channels(channels, Direction::OUT, Pull::OFF, initials, drive)
+    }
+
+    /// Sets up a channel unless it is already set up with the requested direction.
+    ///
+    /// Code that shares a `GPIO` object with its caller cannot know whether a
+    /// channel was already set up. Setting it up again would briefly release an
+    /// output, so this method leaves a channel that already has `direction`
+    /// untouched. Otherwise, the channel is set up like `setup` does, without a
+    /// pull resistor or initial level.
+    ///
+    /// Returns the direction of the channel, which is `direction` on success.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to set up.
+    /// * `direction` - `Direction::IN` or `Direction::OUT`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    ///
+    /// // does nothing, the channel is already an output
+    /// gpio.ensure_setup(7, Direction::OUT).unwrap();
+    /// ```
+    pub fn ensure_setup<C: Into<Channel>>(&mut self, channel: C, direction: Direction) -> Result<Direction, Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        if self.app_channel_configuration(ch_info.clone()) == Some(direction.clone()) {
+            return Ok(direction);
+        }
+
+        self.setup_channels(vec![ch_info.channel], direction.clone(), Pull::OFF, vec![None], Drive::PUSH_PULL)?;
+        Ok(direction)
+    }
+
+    /// Switches an output channel to high impedance by making it an input.
+    ///
+    /// The channel stops driving the line, so another device or a pull
+    /// resistor sets its level, which can be read with `input`. The channel
+    /// remembers that it is an output: `output` returns an error until
+    /// `release_tristate` makes it drive the line again. Does nothing if the
+    /// channel is already in tristate. A channel watched by the watchdog must
+    /// keep driving its line and cannot be switched.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - An output channel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    ///
+    /// // let the other side of a bidirectional line answer
+    /// gpio.set_tristate(7).unwrap();
+    /// let answer = gpio.input(7).unwrap();
+    /// gpio.release_tristate(7).unwrap();
+    /// ```
+    pub fn set_tristate<C: Into<Channel>>(&mut self, channel: C) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        match self.app_channel_configuration(ch_info.clone()) {
+            Some(Direction::TRISTATE) => return Ok(()),
+            Some(Direction::OUT) => {}
+            _ => return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel, Direction::OUT))),
+        }
+        if self.watchdog.as_ref().is_some_and(|w| w.watches(&ch_info.channel)) {
+            return Err(Error::msg(format!(
+                "Channel {} is watched by the watchdog and cannot be switched to tristate",
+                ch_info.channel
+            )));
+        }
+
+        self.switch_direction(&ch_info, Direction::IN, None)?;
+        self.channel_configuration
+            .insert(ch_info.channel.clone(), Direction::TRISTATE);
+
+        Ok(())
+    }
+
+    /// Makes a channel switched to high impedance with `set_tristate` drive the line again.
+    ///
+    /// The channel drives the level last written to it with `output` or set up
+    /// as its initial level, or LOW if it has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - A channel in tristate.
+    pub fn release_tristate<C: Into<Channel>>(&mut self, channel: C) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        if self.app_channel_configuration(ch_info.clone()) != Some(Direction::TRISTATE) {
+            return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel, Direction::TRISTATE)));
+        }
+
+        let last_output = self.last_outputs.lock().unwrap().get(&ch_info.channel).cloned();
+        let initial = last_output.map(|level| self.electrical_level(&ch_info.channel, level));
+        self.switch_direction(&ch_info, Direction::OUT, initial)?;
+        self.channel_configuration
+            .insert(ch_info.channel.clone(), Direction::OUT);
+
+        Ok(())
+    }
+
+    // Changes the direction of a channel that stays set up, `initial` is the electrical
+    // level of an output.
+    fn switch_direction(
+        &mut self,
+        ch_info: &ChannelInfo,
+        direction: Direction,
+        initial: Option<Level>,
+    ) -> Result<(), Error> {
+        match self.backend {
+            Backend::SYSFS => {
+                let sysfs_direction = match direction {
+                    Direction::OUT => output_direction(initial),
+                    _ => "in",
+                };
+                write_direction(ch_info.clone(), sysfs_direction.to_string())?;
+            }
+            Backend::CHARDEV => {
+                let drive = self.drive(&ch_info.channel);
+                let line = self.requested_line(ch_info)?;
+                set_request_line(&line.file, ch_info, direction, Pull::OFF, initial, drive)?;
+            }
+            Backend::MOCK => self.mock_pins.export(ch_info, direction, Pull::OFF, initial),
+        }
+
+        Ok(())
+    }
+
+    fn setup_channels(
+        &mut self,
+        channels: Vec<Channel>,
+        direction: Direction,
+        pull_up_down: Pull,
+        initials: Vec<Option<Level>>,
+        drive: Drive,
+    ) -> Result<&mut Self, Error> {
+        if self.backend == Backend::SYSFS {
+            check_sysfs_write_access()?;
+        }
+
+        let ch_infos = self.channels_to_infos(channels, true, false)?;
+
+        // check direction is valid
+        if !direction.is_valid() {
+            return Err(Error::msg("An invalid direction was passed to setup()"));
+        }
+
+        // check if pullup/down is used with output
+        if direction == Direction::OUT && pull_up_down != Pull::OFF {
+            return Err(Error::msg("pull_up_down parameter is not valid for outputs"));
+        }
+
+        if direction != Direction::OUT && initials.iter().any(Option::is_some) {
+            return Err(Error::msg("initial parameter is not valid for inputs"));
+        }
+
+        // the character device backend reports lines in use when requesting them
+        if self.gpio_warnings && self.backend == Backend::SYSFS {
+            for ch_info in ch_infos.clone() {
+                let sysfs_cfg = sysfs_channel_configuration(ch_info.clone());
+                let app_cfg = self.app_channel_configuration(ch_info.clone());
+
+                // warn if channel has been setup external to current program
+                if app_cfg.is_none() && sysfs_cfg.is_some() {
+                    self.warn_in_use(&ch_info.channel);
+                }
+            }
+        }
+
+        // cleanup if the channel is already setup
+        for ch_info in ch_infos.clone() {
+            // if ch_info.channel in channel_configuration:
+            //     cleanup_one(ch_info)
+            if self.channel_configuration.contains_key(&ch_info.channel) {
+                // a channel that keeps its direction stays exported, only its events are stopped
+                if self.app_channel_configuration(ch_info.clone()) == Some(direction.clone())
+                    && self.keeps_sysfs_direction(&ch_info, direction.clone())
+                {
+                    self.events.remove(&ch_info.channel);
+                    continue;
+                }
+                self.cleanup_one(ch_info)?;
+            }
+        }
+
+        for ch_info in ch_infos.iter() {
+            match direction == Direction::OUT && drive == Drive::OPEN_DRAIN {
+                true => self.open_drain.insert(ch_info.channel.clone()),
+                false => self.open_drain.remove(&ch_info.channel),
+            };
+        }
+
+        match direction {
+            Direction::OUT => {
+                if self.backend == Backend::CHARDEV {
+                    let line_initials = ch_infos
+                        .iter()
+                        .zip(initials.iter())
+                        .map(|(ch_info, initial)| {
+                            initial.clone().map(|initial| self.electrical_level(&ch_info.channel, initial))
+                        })
+                        .collect();
+                    self.request_output_lines(ch_infos.clone(), line_initials)?;
+                }
+                for (ch_info, initial) in ch_infos.into_iter().zip(initials) {
+                    self.setup_single_out(ch_info, initial)?;
+                }
+            }
+            _ => {
+                for ch_info in ch_infos {
+                    self.setup_single_in(ch_info, pull_up_down)?;
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Marks channels as active-low, or back as active-high.
+    ///
+    /// The level of an active-low channel is inverted between the program and
+    /// the pin: writing `Level::HIGH` drives the pin low, and a low pin is read
+    /// as `Level::HIGH`. This applies to `output`, `input`, `toggle`, the initial
+    /// level of `setup`, the watchdog's safe levels and pin handles created
+    /// afterwards. Edge detection and its callbacks report electrical levels.
+    ///
+    /// The setting can be made before or after `setup` and is kept until the
+    /// channel is cleaned up.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of channels.
+    /// * `active_low` - `true` to invert the channels, `false` to restore normal levels.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// // a relay that switches on when the pin is driven low
+    /// gpio.set_active_low(vec![7], true).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    /// gpio.output(vec![7], vec![Level::HIGH]).unwrap();
+    /// ```
+    pub fn set_active_low<C: Into<Channel>, I: IntoIterator<Item = C>>(
+        &mut self,
+        channels: I,
+        active_low: bool,
+    ) -> Result<(), Error> {
+        let channels = channels.into_iter().map(Into::into).collect();
+        let ch_infos = self.channels_to_infos(channels, true, false)?;
+
+        for ch_info in ch_infos {
+            match active_low {
+                true => self.active_low.insert(ch_info.channel),
+                false => self.active_low.remove(&ch_info.channel),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Gives a channel a name, e.g. the device it is wired to.
+    ///
+    /// The label is only kept by this object: it is included in the snapshots of
+    /// `export_config` and restored by `import_config`, so a restarted process can
+    /// tell its channels apart. Like `set_active_low`, the label is kept until the
+    /// channel is cleaned up.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to label.
+    /// * `label` - The name of the channel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// gpio.set_label(7, "pump").unwrap();
+    /// assert_eq!(gpio.label(7), Some("pump"));
+    /// ```
+    pub fn set_label<C: Into<Channel>>(&mut self, channel: C, label: &str) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        self.labels.insert(ch_info.channel, String::from(label));
+
+        Ok(())
+    }
+
+    /// Returns the label given to a channel with `set_label`, or `None` if it has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel.
+    pub fn label<C: Into<Channel>>(&self, channel: C) -> Option<&str> {
+        let ch_info = self.channel_to_info(&channel.into(), false, false).ok()?;
+        self.labels.get(&ch_info.channel).map(String::as_str)
+    }
+
+    /// Sets up a single channel as an output and returns a handle to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to setup.
+    /// * `initial` - An optional initial level.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let led = gpio.setup_output_pin(7, Some(Level::LOW)).unwrap();
+    /// led.set_high().unwrap();
+    /// ```
+    pub fn setup_output_pin<C: Into<Channel>>(&mut self, channel: C, initial: Option<Level>) -> Result<OutputPin, Error> {
+        let channel = channel.into();
+        self.setup(vec![channel.clone()], Direction::OUT, None, initial)?;
+
+        let ch_info = self.channel_to_info(&channel, true, false)?;
+        let io = self.pin_io(&ch_info)?;
+        let active_low = self.active_low.contains(&ch_info.channel);
+        Ok(OutputPin::new(ch_info, io, active_low))
+    }
+
+    /// Sets up four channels as a software SPI bus and returns it, see `BitBangSpi`.
+    ///
+    /// SCK is set to the idle level of `mode`, CS to HIGH and MOSI to LOW. MISO
+    /// is set up as an input without a pull resistor.
+    ///
+    /// # Arguments
+    ///
+    /// * `sck` - The clock output.
+    /// * `mosi` - The data output to the peripheral.
+    /// * `miso` - The data input from the peripheral.
+    /// * `cs` - The active-low chip select output.
+    /// * `mode` - The clock polarity and phase, e.g. `SpiMode::MODE0`.
+    /// * `bit_order` - `BitOrder::MSB_FIRST` or `BitOrder::LSB_FIRST`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, BitOrder, Mode, SpiMode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// // the pins of SPI1 on the 40-pin header
+    /// let spi = gpio.setup_spi(23, 19, 21, 24, SpiMode::MODE0, BitOrder::MSB_FIRST).unwrap();
+    /// ```
+    pub fn setup_spi<C: Into<Channel>>(
+        &mut self,
+        sck: C,
+        mosi: C,
+        miso: C,
+        cs: C,
+        mode: SpiMode,
+        bit_order: BitOrder,
+    ) -> Result<BitBangSpi, Error> {
+        let cs = self.setup_output_pin(cs, Some(Level::HIGH))?;
+        let sck = self.setup_output_pin(sck, Some(mode.idle()))?;
+        let mosi = self.setup_output_pin(mosi, Some(Level::LOW))?;
+        let miso = self.setup_input_pin(miso, None)?;
+
+        Ok(BitBangSpi::new(sck, mosi, miso, cs, mode, bit_order))
+    }
+
+    /// Sets up an input channel whose level is debounced, e.g. for a mechanical button.
+    ///
+    /// `input` then only reports a level once it has been held for `debounce_ms`.
+    /// The character device backend debounces in the kernel. On kernels without
+    /// support for it, and on the sysfs and mock backends, `input` samples the
+    /// channel every poll interval (see `set_poll_interval`) until the level has
+    /// been stable, so it blocks for at least `debounce_ms`. Pin handles and
+    /// edge detection are not debounced in software.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to setup.
+    /// * `pull_up_down` - The pull resistor of the channel, or `None` for `Pull::OFF`.
+    /// * `debounce_ms` - How long a level must be held before it is reported, in milliseconds.
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Level, Mode, Pull};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup_input_debounced(12, Pull::UP, 20).unwrap();
+    ///
+    /// if gpio.input(12).unwrap() == Level::LOW {
+    ///     println!("Button pressed");
+    /// }
+    /// ```
+    pub fn setup_input_debounced<C: Into<Channel>, P: Into<Option<Pull>>>(
+        &mut self,
+        channel: C,
+        pull_up_down: P,
+        debounce_ms: u64,
+    ) -> Result<&mut Self, Error> {
+        self.setup_debounced(channel.into(), pull_up_down.into(), debounce_ms, None)
+    }
+
+    /// Sets up a debounced input channel that is sampled at its own interval.
+    ///
+    /// Same as `setup_input_debounced`, but when the channel is debounced in
+    /// software, `input` samples it every `interval` instead of the poll interval
+    /// set with `set_poll_interval`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to setup.
+    /// * `pull_up_down` - The pull resistor of the channel, or `None` for `Pull::OFF`.
+    /// * `debounce_ms` - How long a level must be held before it is reported, in milliseconds.
+    /// * `interval` - The time to sleep between two samples of the channel.
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    pub fn setup_input_debounced_with_interval<C: Into<Channel>, P: Into<Option<Pull>>>(
+        &mut self,
+        channel: C,
+        pull_up_down: P,
+        debounce_ms: u64,
+        interval: Duration,
+    ) -> Result<&mut Self, Error> {
+        self.setup_debounced(channel.into(), pull_up_down.into(), debounce_ms, Some(interval))
+    }
+
+    fn setup_debounced(
+        &mut self,
+        channel: Channel,
+        pull_up_down: Option<Pull>,
+        debounce_ms: u64,
+        interval: Option<Duration>,
+    ) -> Result<&mut Self, Error> {
+        self.setup(vec![channel.clone()], Direction::IN, pull_up_down, None)?;
+
+        let ch_info = self.channel_to_info(&channel, true, false)?;
+        let debounce = Duration::from_millis(debounce_ms);
+        if self.backend == Backend::CHARDEV {
+            let line = self.requested_line(&ch_info)?;
+            if set_line_debounce(&ch_info, &line.file, debounce).is_ok() {
+                return Ok(self);
+            }
+        }
+
+        self.debounce.insert(ch_info.channel, (debounce, interval));
+        Ok(self)
+    }
+
+    /// Sets up a single channel as an input and returns a handle to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to setup.
+    /// * `pull_up_down` - The pull resistor, see `setup`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let button = gpio.setup_input_pin(12, None).unwrap();
+    /// println!("Button is {}", if button.is_high().unwrap() { "released" } else { "pressed" });
+    /// ```
+    pub fn setup_input_pin<C: Into<Channel>, P: Into<Option<Pull>>>(
+        &mut self,
+        channel: C,
+        pull_up_down: P,
+    ) -> Result<InputPin, Error> {
+        let channel = channel.into();
+        self.setup(vec![channel.clone()], Direction::IN, pull_up_down, None)?;
+
+        let ch_info = self.channel_to_info(&channel, true, false)?;
+        let io = self.pin_io(&ch_info)?;
+        let active_low = self.active_low.contains(&ch_info.channel);
+        Ok(InputPin::new(ch_info, io, active_low))
+    }
+
+    /// Returns channels to the unexported state, including channels left exported by another process.
+    ///
+    /// `setup` only cleans up channels set up by this object, so a channel left
+    /// exported by an earlier run keeps its stale direction and kernel state.
+    /// Call `reset` before `setup` to unexport such channels first. With the
+    /// character device backend, only the channels of this object are released,
+    /// since the kernel frees the lines of other processes when they exit.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of channels to reset.
+    ///
+    /// Returns a reference to `self`, so calls can be chained.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.reset(vec![7]).unwrap().setup(vec![7], Direction::OUT, None, None).unwrap();
+    /// ```
+    pub fn reset<C: Into<Channel>, I: IntoIterator<Item = C>>(&mut self, channels: I) -> Result<&mut Self, Error> {
+        let channels: Vec<Channel> = channels.into_iter().map(Into::into).collect();
+        let ch_infos = self.channels_to_infos(channels, true, false)?;
+
+        for ch_info in ch_infos {
+            if self.channel_configuration.contains_key(&ch_info.channel) {
+                self.cleanup_one(ch_info.clone())?;
+            }
+
+            if self.backend == Backend::SYSFS {
+                match sysfs_channel_configuration(ch_info.clone()) {
+                    Some(Direction::HARD_PWM) => {
+                        disable_pwm(&ch_info)?;
+                        unexport_pwm(&ch_info)?;
+                    }
+                    Some(_) => unexport_gpio(ch_info, self.export_retry)?,
+                    None => {}
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Cleans up channels at the end of the program.
+    ///
+    /// Once the last set up channel is cleaned up, the pin numbering mode is
+    /// reset as well, just like after `cleanup_all`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - An optional list of channels to cleanup, as numbers or pin names. If no channel is provided,
+    ///   all channels are cleaned. As `None` carries no channel type, use `cleanup_all` instead of `cleanup(None)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::TEGRA_SOC).unwrap();
+    /// gpio.setup(vec!["GP66"], Direction::OUT, None, None).unwrap();
+    ///
+    /// gpio.cleanup(Some(vec!["GP66"])).unwrap();
+    /// ```
+    pub fn cleanup<C: Into<Channel>, I: IntoIterator<Item = C>>(&mut self, channels: Option<I>) -> Result<(), Error> {
+        // warn if no channel is setup
+        if self.gpio_mode.is_none() {
+            self.warn("No channels have been set up yet - nothing to clean up! Try cleaning up at the end of your program instead!");
+            return Ok(());
+        }
+
+        // clean all channels if no channel param provided
+        let Some(channels) = channels else {
+            return self.cleanup_all();
+        };
+
+        let channels = channels.into_iter().map(Into::into).collect();
+        let ch_infos = self.channels_to_infos(channels, false, false)?;
+        let mut cleaned = false;
+        for ch_info in ch_infos {
+            self.active_low.remove(&ch_info.channel);
+            self.labels.remove(&ch_info.channel);
+            if self.channel_configuration.contains_key(&ch_info.channel) {
+                self.cleanup_one(ch_info)?;
+                cleaned = true;
+            }
+        }
+
+        if cleaned && self.channel_configuration.is_empty() {
+            self.reset_mode();
+        }
+
+        Ok(())
+    }
+
+    /// Cleans up all channels at the end of the program and resets the pin numbering mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    ///
+    /// gpio.cleanup_all().unwrap();
+    /// ```
+    pub fn cleanup_all(&mut self) -> Result<(), Error> {
+        if self.gpio_mode.is_none() {
+            self.warn("No channels have been set up yet - nothing to clean up! Try cleaning up at the end of your program instead!");
+            return Ok(());
+        }
+
+        self.cleanup_channels_only()?;
+        self.reset_mode();
+
+        Ok(())
+    }
+
+    /// Cleans up all configured channels but keeps the pin numbering mode set.
+    ///
+    /// Unlike `cleanup_all`, the GPIO object can be used again right away
+    /// without calling `setmode`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    ///
+    /// gpio.cleanup_channels_only().unwrap();
+    /// gpio.setup(vec![7], Direction::IN, None, None).unwrap();
+    /// ```
+    pub fn cleanup_channels_only(&mut self) -> Result<(), Error> {
+        for (channel, _) in self.channel_configuration.clone().iter() {
+            let ch_info = self.channel_to_info(channel, false, false)?;
+            self.cleanup_one(ch_info)?;
+        }
+        self.active_low.clear();
+        self.labels.clear();
+
+        Ok(())
+    }
+
+    /// Enables a software watchdog that drives outputs to a safe state if it is not kicked in time.
+    ///
+    /// A background thread is started that waits for `kick_watchdog` to be
+    /// called. If it is not called within `timeout`, the thread drives every
+    /// channel in `safe_states` to its safe level. Kicking the watchdog again
+    /// re-arms it. Enabling the watchdog again replaces the previous one.
+    ///
+    /// Cleaning up a channel listed in `safe_states` disables the watchdog.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum time allowed between two kicks.
+    /// * `safe_states` - The channels to drive and their safe levels. The channels must be set up as outputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    ///
+    /// gpio.enable_watchdog(Duration::from_millis(100), vec![(7, Level::LOW)]).unwrap();
+    /// gpio.output(vec![7], vec![Level::HIGH]).unwrap();
+    /// gpio.kick_watchdog().unwrap();
+    /// gpio.disable_watchdog();
+    /// ```
+    pub fn enable_watchdog<C: Into<Channel>, I: IntoIterator<Item = (C, Level)>>(
+        &mut self,
+        timeout: Duration,
+        safe_states: I,
+    ) -> Result<(), Error> {
+        let mut watched: Vec<SafeState> = Vec::new();
+        for (channel, level) in safe_states {
+            let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+            let app_cfg = self.app_channel_configuration(ch_info.clone());
+            if app_cfg.is_none() || app_cfg.unwrap() != Direction::OUT {
+                return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel, Direction::OUT)));
+            }
+            let line = match self.backend {
+                Backend::SYSFS if self.open_drain.contains(&ch_info.channel) => {
+                    return Err(Error::msg(format!(
+                        "Channel {} is an open-drain output, which the watchdog only supports with the character device backend",
+                        ch_info.channel
+                    )));
+                }
+                Backend::SYSFS => None,
+                Backend::CHARDEV => {
+                    let line = self.requested_line(&ch_info)?;
+                    Some((line.file.try_clone()?, line.index))
+                }
+                Backend::MOCK => return Err(unsupported_by_mock("The watchdog")),
+            };
+            watched.push(SafeState {
+                level: self.electrical_level(&ch_info.channel, level.clone()),
+                logical_level: level,
+                ch_info,
+                line,
+            });
+        }
+
+        // stop a previous watchdog before starting the new one
+        self.watchdog = None;
+        self.watchdog = Some(Watchdog::start(
+            timeout,
+            watched,
+            self.last_outputs.clone(),
+            self.gpio_warnings.then(|| self.warning_handler.clone()),
+        ));
+
+        Ok(())
+    }
+
+    /// Resets the watchdog timer, preventing the outputs from being driven to their safe state.
+    pub fn kick_watchdog(&self) -> Result<(), Error> {
+        match &self.watchdog {
+            Some(watchdog) => {
+                watchdog.kick();
+                Ok(())
+            }
+            None => Err(Error::msg("The watchdog is not enabled")),
+        }
+    }
+
+    /// Stops the watchdog. The outputs are left at their current levels.
+    pub fn disable_watchdog(&mut self) {
+        self.watchdog = None;
+    }
+
+    /// Returns a summary of the detected platform: the model and its `JetsonInfo`, one field per line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::GPIO;
+    ///
+    /// let gpio = GPIO::new();
+    /// print!("{}", gpio.platform_summary());
+    /// ```
+    pub fn platform_summary(&self) -> String {
+        format!("Model: {}\n{}", self.model, self.jetson_info)
+    }
+
+    /// Checks whether the detected model is the given one, e.g. `"JETSON_NANO"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model name, see `new_for_model`
+    pub fn is_model(&self, model: &str) -> bool {
+        self.model == model
+    }
+
+    /// Checks that the process can control the GPIOs with the backend of this object.
+    ///
+    /// `setup` runs the same check, but calling it at startup lets a program
+    /// fail early with a helpful message. With the sysfs backend, the `export`
+    /// and `unexport` files must be writable. With the character device backend,
+    /// the GPIO chip devices of the board must be writable. The mock backend
+    /// always has access. If the interface is present but not writable, the
+    /// error is `GpioError::NO_WRITE_ACCESS`, and if it is missing, it is
+    /// `GpioError::NO_INTERFACE`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, GpioError};
+    ///
+    /// let gpio = GPIO::new();
+    /// match gpio.check_write_access() {
+    ///     Ok(()) => {}
+    ///     Err(GpioError::NO_WRITE_ACCESS) => {
+    ///         eprintln!("Run with sudo or add your user to the gpio group");
+    ///         std::process::exit(1);
+    ///     }
+    ///     Err(e) => {
+    ///         eprintln!("{}", e);
+    ///         std::process::exit(1);
+    ///     }
+    /// }
+    /// ```
+    pub fn check_write_access(&self) -> Result<(), GpioError> {
+        match self.backend {
+            Backend::SYSFS => check_sysfs_write_access(),
+            Backend::CHARDEV => {
+                let mut gpio_chip_devs: Vec<&String> = self
+                    .channel_data_by_mode
+                    .get(&Mode::BOARD)
+                    .into_iter()
+                    .flat_map(|board_data| board_data.values())
+                    .filter_map(|ch_info| ch_info.gpio_chip_dev.as_ref())
+                    .collect();
+                gpio_chip_devs.sort();
+                gpio_chip_devs.dedup();
+
+                for gpio_chip_dev in gpio_chip_devs {
+                    match fs::OpenOptions::new().read(true).write(true).open(gpio_chip_dev) {
+                        Ok(_) => {}
+                        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                            return Err(GpioError::NO_WRITE_ACCESS);
+                        }
+                        Err(_) => return Err(GpioError::NO_INTERFACE(gpio_chip_dev.clone())),
+                    }
+                }
+
+                Ok(())
+            }
+            Backend::MOCK => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the process can control the GPIOs, see `check_write_access`.
+    pub fn has_write_access(&self) -> bool {
+        self.check_write_access().is_ok()
+    }
+
+    /// Returns the GPIO chips the channels were resolved against, as (sysfs name, base, ngpio).
+    ///
+    /// The global GPIO number of a channel is the base of its chip plus the
+    /// line offset of the pin. When the wrong pin toggles, comparing these
+    /// numbers with `/sys/class/gpio/gpiochip*/base` shows whether a chip was
+    /// matched with the wrong base, e.g. 224 instead of 169.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::GPIO;
+    ///
+    /// let gpio = GPIO::new();
+    /// for (name, base, ngpio) in gpio.chip_info() {
+    ///     println!("{}: base {}, {} lines", name, base, ngpio);
+    /// }
+    /// ```
+    pub fn chip_info(&self) -> Vec<(String, u32, u32)> {
+        self.gpio_chips.clone()
+    }
+
+    /// Runs a diagnostic check of the environment and returns a report.
+    ///
+    /// The report contains the detected model, whether the GPIO sysfs interface
+    /// is writable, the resolved GPIO chips, the pins whose GPIO or PWM chip
+    /// could not be resolved, and the pin numbering modes that have data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::GPIO;
+    ///
+    /// let gpio = GPIO::new();
+    /// println!("{}", gpio.self_test());
+    /// ```
+    pub fn self_test(&self) -> SelfTestReport {
+        let write_access = check_sysfs_write_access().map_err(|e| e.to_string());
+
+        let mut unresolved_gpio_pins: Vec<u32> = Vec::new();
+        let mut unresolved_pwm_pins: Vec<u32> = Vec::new();
+        if let Some(board_data) = self.channel_data_by_mode.get(&Mode::BOARD) {
+            for ch_info in board_data.values() {
+                let Channel::Number(board) = ch_info.channel else {
+                    continue;
+                };
+                if ch_info.gpio_chip_dir.is_empty() {
+                    unresolved_gpio_pins.push(board);
+                }
+                if ch_info.pwm_id.is_some() && ch_info.pwm_chip_dir.is_none() {
+                    unresolved_pwm_pins.push(board);
+                }
+            }
+        }
+        unresolved_gpio_pins.sort();
+        unresolved_pwm_pins.sort();
+
+        let modes: Vec<Mode> = [Mode::BOARD, Mode::BCM, Mode::TEGRA_SOC, Mode::CVM]
+            .into_iter()
+            .filter(|mode| self.channel_data_by_mode.get(mode).is_some_and(|data| !data.is_empty()))
+            .collect();
+
+        SelfTestReport {
+            model: self.model.clone(),
+            write_access,
+            gpio_chips: self.gpio_chips.clone(),
+            unresolved_gpio_pins,
+            unresolved_pwm_pins,
+            modes,
+        }
+    }
+
+    /// Returns a snapshot of the current pin numbering mode and channel configuration.
+    ///
+    /// The snapshot can be restored with `import_config`, for example after a
+    /// process restart.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    ///
+    /// let snapshot = gpio.export_config();
+    /// assert_eq!(snapshot.channels.len(), 1);
+    /// ```
+    pub fn export_config(&self) -> ConfigSnapshot {
+        let mut channels: Vec<ChannelSnapshot> = self
+            .channel_configuration
+            .iter()
+            .map(|(channel, direction)| ChannelSnapshot {
+                channel: channel.clone(),
+                direction: direction.clone(),
+                active_low: self.active_low.contains(channel),
+                label: self.labels.get(channel).cloned(),
+            })
+            .collect();
+        channels.sort_by(|a, b| a.channel.cmp(&b.channel));
+
+        ConfigSnapshot {
+            mode: self.gpio_mode,
+            channels,
+        }
+    }
+
+    /// Restores a configuration previously created with `export_config`.
+    ///
+    /// The pin numbering mode is set from the snapshot and every channel is
+    /// adopted as configured by this process. The channels are neither exported
+    /// nor written to, so their current levels are left untouched. Every channel
+    /// must already be exported with the direction recorded in the snapshot,
+    /// otherwise an error is returned and nothing is adopted.
+    ///
+    /// Only the sysfs backend can adopt channels, because lines requested from
+    /// the character devices are released when the process exits.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The configuration to restore.
+    pub fn import_config(&mut self, snapshot: ConfigSnapshot) -> Result<(), Error> {
+        if self.backend != Backend::SYSFS {
+            return Err(Error::msg("import_config is only supported by the sysfs backend"));
+        }
+
+        if let Some(mode) = snapshot.mode {
+            self.setmode(mode)?;
+        } else if !snapshot.channels.is_empty() {
+            return Err(Error::msg("The snapshot contains channels but no pin numbering mode"));
+        }
+
+        let mut adopted: Vec<ChannelSnapshot> = Vec::new();
+        for ch_snapshot in snapshot.channels {
+            let ch_info = self.channel_to_info(&ch_snapshot.channel, true, false)?;
+            // a tristated output is an input as far as sysfs is concerned
+            let expected = match ch_snapshot.direction {
+                Direction::TRISTATE => Direction::IN,
+                ref direction => direction.clone(),
+            };
+            if sysfs_channel_configuration(ch_info) != Some(expected) {
+                return Err(Error::msg(format!(
+                    "Channel {} is not exported with the direction recorded in the snapshot",
+                    ch_snapshot.channel
+                )));
+            }
+            adopted.push(ch_snapshot);
+        }
+
+        for ch_snapshot in adopted {
+            if ch_snapshot.active_low {
+                self.active_low.insert(ch_snapshot.channel.clone());
+            }
+            if let Some(label) = ch_snapshot.label {
+                self.labels.insert(ch_snapshot.channel.clone(), label);
+            }
+            self.channel_configuration.insert(ch_snapshot.channel, ch_snapshot.direction);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current value of the specified channel.
+    ///
+    /// Return either `Level::HIGH` or `Level::LOW`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to read from.
+    pub fn input<C: Into<Channel>>(&self, channel: C) -> Result<Level, Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+
+        let app_cfg = self.app_channel_configuration(ch_info.clone());
+        if app_cfg.is_none() || ![Direction::IN, Direction::OUT, Direction::TRISTATE].contains(&app_cfg.unwrap()) {
+            return Err(Error::new(GpioError::NOT_CONFIGURED(ch_info.channel)));
+        }
+
+        if let Some(&(debounce, interval)) = self.debounce.get(&ch_info.channel) {
+            return self.read_debounced(ch_info, debounce, interval.unwrap_or(self.poll_interval));
+        }
+
+        match self.read_channel(ch_info)?.as_str() {
+            "0" => Ok(Level::LOW),
+            _ => Ok(Level::HIGH),
+        }
+    }
+
+    // Samples the channel every `interval` until its level has been held for `debounce`.
+    fn read_debounced(&self, ch_info: ChannelInfo, debounce: Duration, interval: Duration) -> Result<Level, Error> {
+        let read_level = || -> Result<Level, Error> {
+            match self.read_channel(ch_info.clone())?.as_str() {
+                "0" => Ok(Level::LOW),
+                _ => Ok(Level::HIGH),
+            }
+        };
+
+        let mut level = read_level()?;
+        let mut held_since = Instant::now();
+        while held_since.elapsed() < debounce {
+            thread::sleep(interval);
+            let current = read_level()?;
+            if current != level {
+                level = current;
+                held_since = Instant::now();
+            }
+        }
+
+        Ok(level)
+    }
+
+    /// Returns the current values of several channels, in the order of `channels`.
+    ///
+    /// With the character device backend, channels of the same GPIO chip that were
+    /// set up in one call of `setup` share a line request and are sampled with a
+    /// single ioctl, giving a coherent snapshot, e.g. of a parallel bus. Channels of
+    /// different requests are sampled with one ioctl per request. Other backends, and
+    /// channels with software debouncing, are read one after the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of channels to read from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7, 11], Direction::IN, None, None).unwrap();
+    ///
+    /// let levels = gpio.input_many(vec![7, 11]).unwrap();
+    /// ```
+    pub fn input_many<C: Into<Channel>, I: IntoIterator<Item = C>>(&self, channels: I) -> Result<Vec<Level>, Error> {
+        let channels: Vec<Channel> = channels.into_iter().map(Into::into).collect();
+        let debounced = channels.iter().any(|channel| self.debounce.contains_key(channel));
+        if self.backend != Backend::CHARDEV || debounced {
+            return channels.into_iter().map(|channel| self.input(channel)).collect();
+        }
+
+        let mut ch_infos: Vec<ChannelInfo> = Vec::new();
+        for channel in channels.iter() {
+            let ch_info = self.channel_to_info(channel, true, false)?;
+            let app_cfg = self.app_channel_configuration(ch_info.clone());
+            if app_cfg.is_none() || ![Direction::IN, Direction::OUT, Direction::TRISTATE].contains(&app_cfg.unwrap()) {
+                return Err(Error::new(GpioError::NOT_CONFIGURED(ch_info.channel)));
+            }
+            ch_infos.push(ch_info);
         }
+
+        // collect the lines to sample per request, then read each request once
+        let mut requests: Vec<(&RequestedLine, u64)> = Vec::new();
+        for ch_info in ch_infos.iter() {
+            let line = self.requested_line(ch_info)?;
+            match requests.iter_mut().find(|(other, _)| Arc::ptr_eq(&other.file, &line.file)) {
+                Some((_, mask)) => *mask |= 1 << line.index,
+                None => requests.push((line, 1 << line.index)),
+            }
+        }
+        let mut snapshots: Vec<(&RequestedLine, u64)> = Vec::new();
+        for (line, mask) in requests {
+            snapshots.push((line, get_line_values(&line.file, mask)?));
+        }
+
+        let mut levels: Vec<Level> = Vec::new();
+        for ch_info in ch_infos.iter() {
+            let line = self.requested_line(ch_info)?;
+            let (_, bits) = snapshots.iter().find(|(other, _)| Arc::ptr_eq(&other.file, &line.file)).unwrap();
+            let level = match bits & (1 << line.index) {
+                0 => Level::LOW,
+                _ => Level::HIGH,
+            };
+            levels.push(self.electrical_level(&ch_info.channel, level));
+        }
+
+        Ok(levels)
     }
 
-    fn channel_to_info_lookup(
-        &self,
-        channel: u32,
-        need_gpio: bool,
-        need_pwm: bool,
-    ) -> Result<ChannelInfo, Error> {
-        if !self.channel_data.contains_key(&channel) {
-            return Err(Error::msg(format!(
-                "The channel sent is invalid: {}",
-                channel
-            )));
+    /// Drives the level of a simulated input channel of the mock backend.
+    ///
+    /// Subsequent calls to `input` return the given level until it is driven again.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The input channel to drive.
+    /// * `value` - The level seen by the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Backend, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+    ///
+    /// gpio.set_mock_input(12, Level::HIGH).unwrap();
+    /// assert!(gpio.input(12).unwrap() == Level::HIGH);
+    /// ```
+    pub fn set_mock_input<C: Into<Channel>>(&self, channel: C, value: Level) -> Result<(), Error> {
+        if self.backend != Backend::MOCK {
+            return Err(Error::msg("set_mock_input is only supported by the mock backend"));
+        }
+
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        // a tristated output is driven from outside like an input
+        if self.app_channel_configuration(ch_info.clone()) != Some(Direction::TRISTATE) {
+            self.check_input(&ch_info)?;
         }
 
-        let ch_info = self.channel_data.get(&channel).unwrap().clone();
+        self.mock_pins.set(&ch_info, Direction::IN, value)
+    }
 
-        if need_gpio && ch_info.gpio_chip_dir == "" {
-            return Err(Error::msg(format!("Channel {} is not a GPIO", channel)));
+    /// Returns the current value of the specified channel, rejecting unexpected values.
+    ///
+    /// Unlike `input`, which reports anything other than `0` as `Level::HIGH`,
+    /// this returns an error if the value read from sysfs is not exactly `0` or `1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to read from.
+    pub fn input_strict<C: Into<Channel>>(&self, channel: C) -> Result<Level, Error> {
+        let channel = channel.into();
+        let ch_info = self.channel_to_info(&channel, true, false)?;
+
+        let app_cfg = self.app_channel_configuration(ch_info.clone());
+        if app_cfg.is_none() || ![Direction::IN, Direction::OUT, Direction::TRISTATE].contains(&app_cfg.unwrap()) {
+            return Err(Error::new(GpioError::NOT_CONFIGURED(ch_info.channel)));
         }
 
-        if need_pwm && ch_info.pwm_chip_dir.is_none() {
-            return Err(Error::msg(format!("Channel {} is not a PWM", channel)));
+        match self.read_channel(ch_info)?.as_str() {
+            "0" => Ok(Level::LOW),
+            "1" => Ok(Level::HIGH),
+            value => Err(Error::msg(format!(
+                "Unexpected value read from channel {}: {:?}",
+                channel, value
+            ))),
         }
+    }
 
-        Ok(ch_info)
+    /// Waits until the channel has held the given level continuously for a duration.
+    ///
+    /// The channel is polled every poll interval (see `set_poll_interval`) and
+    /// the stability timer restarts whenever the level changes. Returns `true`
+    /// once the level has been stable for `stable_for`, or `false` if the
+    /// timeout expired first.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to watch.
+    /// * `level` - The level the channel must settle at.
+    /// * `stable_for` - How long the level must be held without change.
+    /// * `timeout` - An optional maximum time to wait. If `None`, waits indefinitely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::IN, None, None).unwrap();
+    ///
+    /// let settled = gpio
+    ///     .wait_for_stable(7, Level::HIGH, Duration::from_millis(50), Some(Duration::from_secs(1)))
+    ///     .unwrap();
+    /// ```
+    pub fn wait_for_stable<C: Into<Channel>>(
+        &self,
+        channel: C,
+        level: Level,
+        stable_for: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<bool, Error> {
+        self.wait_for_stable_with_interval(channel, level, stable_for, timeout, self.poll_interval)
     }
 
-    fn channel_to_info(
+    /// Waits until the channel has held the given level for a duration, polling at the given interval.
+    ///
+    /// Same as `wait_for_stable`, but the channel is polled every `interval`
+    /// instead of the poll interval set with `set_poll_interval`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to watch.
+    /// * `level` - The level the channel must settle at.
+    /// * `stable_for` - How long the level must be held without change.
+    /// * `timeout` - An optional maximum time to wait. If `None`, waits indefinitely.
+    /// * `interval` - The time to sleep between two reads of the channel.
+    pub fn wait_for_stable_with_interval<C: Into<Channel>>(
         &self,
-        channel: u32,
-        need_gpio: bool,
-        need_pwm: bool,
-    ) -> Result<ChannelInfo, Error> {
-        self.validate_mode_set()?;
-        self.channel_to_info_lookup(channel, need_gpio, need_pwm)
+        channel: C,
+        level: Level,
+        stable_for: Duration,
+        timeout: Option<Duration>,
+        interval: Duration,
+    ) -> Result<bool, Error> {
+        let channel = channel.into();
+        let start = Instant::now();
+        let mut held_since: Option<Instant> = None;
+
+        loop {
+            let now = Instant::now();
+            if self.input(channel.clone())? == level {
+                let since = *held_since.get_or_insert(now);
+                if now.duration_since(since) >= stable_for {
+                    return Ok(true);
+                }
+            } else {
+                held_since = None;
+            }
+
+            if let Some(timeout) = timeout {
+                if now.duration_since(start) >= timeout {
+                    return Ok(false);
+                }
+            }
+
+            thread::sleep(interval);
+        }
     }
 
-    fn channels_to_infos(
+    /// Writes a value to channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of channels to write to.
+    /// * `values` - A list of values to write to the channels. Must be either HIGH or LOW.
+    ///   A single value is written to every channel, otherwise there must be one value per channel.
+    ///
+    /// # Example
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7, 11, 13], Direction::OUT, None, None).unwrap();
+    /// gpio.output(vec![7, 11], vec![Level::HIGH, Level::LOW]).unwrap();
+    ///
+    /// // drive all channels LOW
+    /// gpio.output(vec![7, 11, 13], vec![Level::LOW]).unwrap();
+    /// ```
+    pub fn output<C: Into<Channel>, I: IntoIterator<Item = C>>(
         &self,
-        channels: Vec<u32>,
-        need_gpio: bool,
-        need_pwm: bool,
-    ) -> Result<Vec<ChannelInfo>, Error> {
-        self.validate_mode_set()?;
-        let mut ret: Vec<ChannelInfo> = Vec::new();
-        for channel in channels {
-            ret.push(self.channel_to_info_lookup(channel, need_gpio, need_pwm)?);
+        channels: I,
+        values: Vec<Level>,
+    ) -> Result<(), Error> {
+        for (ch_info, value) in self.output_targets(channels, values)? {
+            self.write_channel(ch_info, value)?;
         }
 
-        Ok(ret)
+        Ok(())
     }
 
-    fn app_channel_configuration(&self, ch_info: ChannelInfo) -> Option<Direction> {
-        // """Return the current configuration of a channel as requested by this
-        // module in this process. Any of IN, OUT, or None may be returned."""
+    /// Writes values to channels as close to simultaneously as possible.
+    ///
+    /// Takes the same arguments as `output`. All channels are checked before any
+    /// of them is written, and the values are then written as follows:
+    ///
+    /// * Character device backend: output channels of the same GPIO chip that were
+    ///   set up in one call of `setup` share a line request and are written with a
+    ///   single ioctl, so they change at the same time. Channels of different
+    ///   requests are written with one ioctl per request, in the order of `channels`.
+    /// * sysfs backend: the value files of all channels are opened first, and the
+    ///   values are then written back to back in the order of `channels`. The pins
+    ///   still change one after the other.
+    /// * Mock backend: the values are set in the order of `channels`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Backend, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new_with_backend(Backend::CHARDEV);
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7, 11, 13], Direction::OUT, None, None).unwrap();
+    /// gpio.output_atomic(vec![7, 11, 13], vec![Level::HIGH, Level::LOW, Level::HIGH]).unwrap();
+    /// ```
+    pub fn output_atomic<C: Into<Channel>, I: IntoIterator<Item = C>>(
+        &self,
+        channels: I,
+        values: Vec<Level>,
+    ) -> Result<(), Error> {
+        let targets = self.output_targets(channels, values)?;
 
-        match self.channel_configuration.get(&ch_info.channel) {
-            Some(direction) => Some(direction.clone()),
-            None => None,
+        match self.backend {
+            Backend::CHARDEV => {
+                // the lines of each request with their electrical levels, and the logical levels of their channels
+                type Writes<'a> = (&'a RequestedLine, Vec<(u32, Level)>, Vec<(Channel, Level)>);
+                let mut requests: Vec<Writes> = Vec::new();
+                for (ch_info, value) in targets {
+                    let line = self.requested_line(&ch_info)?;
+                    let level = self.electrical_level(&ch_info.channel, value.clone());
+                    match requests.iter_mut().find(|(other, _, _)| Arc::ptr_eq(&other.file, &line.file)) {
+                        Some((_, levels, values)) => {
+                            levels.push((line.index, level));
+                            values.push((ch_info.channel, value));
+                        }
+                        None => requests.push((line, vec![(line.index, level)], vec![(ch_info.channel, value)])),
+                    }
+                }
+
+                // only levels that reached the pins are remembered
+                for (line, levels, values) in requests {
+                    set_line_values(&line.file, &levels)?;
+                    for (channel, value) in values {
+                        self.record_output(&channel, value);
+                    }
+                }
+            }
+            Backend::SYSFS => {
+                // open the files of channels without a cached one before writing anything,
+                // open-drain channels are written through their direction instead
+                let mut opened: Vec<Option<File>> = Vec::new();
+                for (ch_info, _) in targets.iter() {
+                    if self.value_files.contains_key(&ch_info.channel) || self.open_drain.contains(&ch_info.channel) {
+                        opened.push(None);
+                    } else {
+                        opened.push(Some(open_value_file(ch_info)?));
+                    }
+                }
+                let levels: Vec<Level> = targets
+                    .iter()
+                    .map(|(ch_info, value)| self.electrical_level(&ch_info.channel, value.clone()))
+                    .collect();
+
+                // only levels that reached the pins are remembered
+                for (((ch_info, value), f_value), level) in targets.into_iter().zip(opened.iter()).zip(levels) {
+                    if self.open_drain.contains(&ch_info.channel) {
+                        write_open_drain(ch_info.clone(), level)?;
+                    } else {
+                        let f_value = f_value.as_ref().unwrap_or_else(|| &self.value_files[&ch_info.channel]);
+                        write_value_file(f_value, level.sysfs_str().to_string())?;
+                    }
+                    self.record_output(&ch_info.channel, value);
+                }
+            }
+            Backend::MOCK => {
+                for (ch_info, value) in targets {
+                    self.write_channel(ch_info, value)?;
+                }
+            }
         }
+
+        Ok(())
     }
 
-    fn cleanup_one(&mut self, ch_info: ChannelInfo) {
-        match self.channel_configuration.get(&ch_info.channel) {
-            Some(direction) => {
-                if direction == &Direction::HARD_PWM {
-                    // _disable_pwm(ch_info);
-                    // _unexport_pwm(ch_info);
-                } else {
-                    // event::event_cleanup(ch_info.gpio, ch_info.gpio_name);
-                    unexport_gpio(ch_info.clone());
-                }
+    // Checks the arguments of `output` and pairs each output channel with its value.
+    fn output_targets<C: Into<Channel>, I: IntoIterator<Item = C>>(
+        &self,
+        channels: I,
+        values: Vec<Level>,
+    ) -> Result<Vec<(ChannelInfo, Level)>, Error> {
+        let channels = channels.into_iter().map(Into::into).collect();
+        let ch_infos = self.channels_to_infos(channels, true, false)?;
+
+        // a single value is written to every channel
+        let values = match values.as_slice() {
+            [value] => vec![value.clone(); ch_infos.len()],
+            _ => values,
+        };
+
+        if values.len() != ch_infos.len() {
+            return Err(Error::msg(format!(
+                "{} values provided for {} channels",
+                values.len(),
+                ch_infos.len()
+            )));
+        }
+
+        // check that channels have been set as output
+        for ch_info in ch_infos.iter() {
+            let app_cfg = self.app_channel_configuration(ch_info.clone());
+            if app_cfg.is_none() || app_cfg.unwrap() != Direction::OUT {
+                return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel.clone(), Direction::OUT)));
             }
-            None => {}
         }
 
-        self.channel_configuration.remove(&ch_info.channel);
+        Ok(ch_infos.into_iter().zip(values).collect())
+    }
+
+    /// Writes a value to a single channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to write to.
+    /// * `value` - The value to write. Must be either HIGH or LOW.
+    ///
+    /// # Example
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    /// gpio.output_one(7, Level::HIGH).unwrap();
+    /// ```
+    pub fn output_one<C: Into<Channel>>(&self, channel: C, value: Level) -> Result<(), Error> {
+        self.output(vec![channel], vec![value])
+    }
+
+    /// Returns the level last written to an output channel, without reading the pin.
+    ///
+    /// The level is the one given to `setup` as the initial value, written with
+    /// `output`, `output_atomic`, `toggle` or `blink`, or the safe level driven by
+    /// the watchdog when it trips. Returns `None` if the channel is not set up as
+    /// an output, or nothing has been written to it yet. Levels written through an
+    /// `OutputPin` are not tracked.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The output channel.
+    ///
+    /// # Example
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    /// gpio.toggle(vec![7]).unwrap();
+    /// assert_eq!(gpio.last_output(7), Some(Level::HIGH));
+    /// ```
+    pub fn last_output<C: Into<Channel>>(&self, channel: C) -> Option<Level> {
+        let ch_info = self.channel_to_info(&channel.into(), false, false).ok()?;
+        self.last_outputs.lock().unwrap().get(&ch_info.channel).cloned()
+    }
+
+    /// Inverts the current value of output channels.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - A list of output channels to toggle.
+    ///
+    /// # Example
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    /// gpio.toggle(vec![7]).unwrap();
+    /// ```
+    pub fn toggle<C: Into<Channel>, I: IntoIterator<Item = C>>(&self, channels: I) -> Result<(), Error> {
+        let channels = channels.into_iter().map(Into::into).collect();
+        let ch_infos = self.channels_to_infos(channels, true, false)?;
+
+        // check that channels have been set as output
+        for ch_info in ch_infos.iter() {
+            let app_cfg = self.app_channel_configuration(ch_info.clone());
+            if app_cfg.is_none() || app_cfg.unwrap() != Direction::OUT {
+                return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel.clone(), Direction::OUT)));
+            }
+        }
+
+        for ch_info in ch_infos {
+            let value = match self.read_channel(ch_info.clone())?.as_str() {
+                "0" => Level::HIGH,
+                _ => Level::LOW,
+            };
+            self.write_channel(ch_info, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Blinks an output channel, driving it HIGH for `on_ms` and then LOW for `off_ms`, `times` times.
+    ///
+    /// Blocks until the blinking is done. The channel is left LOW.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The output channel to blink.
+    /// * `times` - The number of blinks.
+    /// * `on_ms` - How long the channel stays HIGH in each blink, in milliseconds.
+    /// * `off_ms` - How long the channel stays LOW in each blink, in milliseconds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    /// gpio.blink(7, 3, 200, 800).unwrap();
+    /// ```
+    pub fn blink<C: Into<Channel>>(&self, channel: C, times: u32, on_ms: u64, off_ms: u64) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        if self.app_channel_configuration(ch_info.clone()) != Some(Direction::OUT) {
+            return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel, Direction::OUT)));
+        }
+
+        for _ in 0..times {
+            self.write_channel(ch_info.clone(), Level::HIGH)?;
+            thread::sleep(Duration::from_millis(on_ms));
+            self.write_channel(ch_info.clone(), Level::LOW)?;
+            thread::sleep(Duration::from_millis(off_ms));
+        }
+
+        Ok(())
+    }
+
+    fn check_input(&self, ch_info: &ChannelInfo) -> Result<(), Error> {
+        if self.app_channel_configuration(ch_info.clone()) != Some(Direction::IN) {
+            return Err(Error::new(GpioError::WRONG_DIRECTION(ch_info.channel.clone(), Direction::IN)));
+        }
+
+        Ok(())
     }
 
-    fn cleanup_all(&mut self) -> Result<(), Error> {
-        for (channel, _) in self.channel_configuration.clone().iter() {
-            let ch_info = self.channel_to_info(*channel, false, false)?;
-            self.cleanup_one(ch_info);
+    /// Enables edge detection on an input channel.
+    ///
+    /// A background thread watches the channel. Every detected edge is recorded
+    /// for `event_detected` and passed to the callbacks together with the level
+    /// read right after the edge. The thread is stopped by `remove_event_detect`
+    /// or when the channel is cleaned up.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The input channel to watch.
+    /// * `edge` - The edge to detect.
+    /// * `callback` - An optional function to call on every detected edge.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Direction, Edge, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![18], Direction::IN, None, None).unwrap();
+    ///
+    /// gpio.add_event_detect(18, Edge::BOTH, Some(Box::new(|channel, level| {
+    ///     println!("Channel {} is now {}", channel, if level == Level::HIGH { "HIGH" } else { "LOW" });
+    /// }))).unwrap();
+    /// ```
+    pub fn add_event_detect<C: Into<Channel>>(
+        &mut self,
+        channel: C,
+        edge: Edge,
+        callback: Option<EventCallback>,
+    ) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        self.check_input(&ch_info)?;
+
+        if self.events.contains_key(&ch_info.channel) {
+            return Err(Error::msg("Conflicting edge detection already enabled for this GPIO channel"));
         }
 
-        self.gpio_mode = None;
+        let line = match self.backend {
+            Backend::SYSFS => None,
+            Backend::CHARDEV => Some(self.requested_line(&ch_info)?.file.clone()),
+            Backend::MOCK => return Err(unsupported_by_mock("Edge detection")),
+        };
+        let detector = EventDetector::start(ch_info.clone(), edge, line)?;
+        if let Some(callback) = callback {
+            detector.add_callback(callback);
+        }
+        self.events.insert(ch_info.channel, detector);
 
         Ok(())
     }
 
-    fn setup_single_out(&mut self, ch_info: ChannelInfo, initial: Option<Level>) {
-        export_gpio(ch_info.clone());
-        write_direction(ch_info.clone(), "out".to_string());
+    /// Adds a callback to a channel that has edge detection enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to add the callback to.
+    /// * `callback` - The function to call on every detected edge.
+    pub fn add_event_callback<C: Into<Channel>>(&self, channel: C, callback: EventCallback) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        self.check_input(&ch_info)?;
 
-        if initial.is_some() {
-            output_one(ch_info.clone(), initial.unwrap());
+        match self.events.get(&ch_info.channel) {
+            Some(detector) => {
+                detector.add_callback(callback);
+                Ok(())
+            }
+            None => Err(Error::msg("Add event detection using add_event_detect first before adding a callback")),
         }
+    }
 
-        self.channel_configuration
-            .insert(ch_info.channel, Direction::OUT);
+    /// Disables edge detection on a channel and stops its background thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to stop watching.
+    pub fn remove_event_detect<C: Into<Channel>>(&mut self, channel: C) -> Result<(), Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        self.events.remove(&ch_info.channel);
+
+        Ok(())
     }
 
-    fn setup_single_in(&mut self, ch_info: ChannelInfo) {
-        export_gpio(ch_info.clone());
-        write_direction(ch_info.clone(), "in".to_string());
+    /// Returns whether an edge was detected on the channel since the last call.
+    ///
+    /// Edge detection must be enabled with `add_event_detect`, otherwise this
+    /// always returns `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to check.
+    pub fn event_detected<C: Into<Channel>>(&self, channel: C) -> Result<bool, Error> {
+        let ch_info = self.channel_to_info(&channel.into(), true, false)?;
+        self.check_input(&ch_info)?;
 
-        self.channel_configuration
-            .insert(ch_info.channel, Direction::IN);
+        match self.events.get(&ch_info.channel) {
+            Some(detector) => Ok(detector.take_event()),
+            None => Ok(false),
+        }
     }
 
-    /// Setup a channel or list of channels with a direction and (optional) pull/up down control and (optional) initial value.
+    /// Blocks until an edge is detected on an input channel.
+    ///
+    /// The channel must not have edge detection enabled with `add_event_detect`.
+    /// Returns the detected transition, or `None` if the timeout expired. When waiting for
+    /// `Edge::BOTH`, the transition is told apart by the level read right after it.
     ///
     /// # Arguments
     ///
-    /// * `channels` - A list of channels to setup.
-    /// * `direction` - `Level::IN` or `Level::OUT`
-    /// * `initial` - An optional initial level for an output channel.
+    /// * `channel` - The input channel to watch.
+    /// * `edge` - The edge to wait for.
+    /// * `timeout` - An optional maximum time to wait. If `None`, waits indefinitely.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use jetson_gpio::{GPIO, Direction, Mode};
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, Direction, Edge, Mode};
     ///
     /// let mut gpio = GPIO::new();
     /// gpio.setmode(Mode::BOARD).unwrap();
-    /// gpio.setup(vec![7], Direction::OUT, None).unwrap();
+    /// gpio.setup(vec![18], Direction::IN, None, None).unwrap();
+    ///
+    /// match gpio.wait_for_edge(18, Edge::FALLING, Some(Duration::from_secs(5))).unwrap() {
+    ///     Some(edge) => println!("Button pressed ({:?})", edge),
+    ///     None => println!("Timed out"),
+    /// }
     /// ```
-    pub fn setup(&mut self, channels: Vec<u32>, direction: Direction, initial: Option<Level>) -> Result<(), Error> {
-        check_write_access()?;
-
-        // if pull_up_down in setup.__defaults__:
-        //     pull_up_down_explicit = False
-        //     pull_up_down = pull_up_down.val
-        // else:
-        //     pull_up_down_explicit = True
-
-        let ch_infos = self.channels_to_infos(channels, true, false)?;
-
-        // check direction is valid
-        if !direction.is_valid() {
-            return Err(Error::msg("An invalid direction was passed to setup()"));
-        }
+    pub fn wait_for_edge<C: Into<Channel>>(
+        &self,
+        channel: C,
+        edge: Edge,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Edge>, Error> {
+        let (ch_info, line) = self.edge_wait_target(channel.into(), edge)?;
+        blocking_wait_for_edge(&ch_info, edge, timeout, line)
+    }
 
-        // // check if pullup/down is used with output
-        // if direction == OUT and pull_up_down != PUD_OFF:
-        //     raise ValueError("pull_up_down parameter is not valid for outputs")
+    /// Blocks until an input channel reads the given level.
+    ///
+    /// Returns `true` right away if the channel is already at `level`. Otherwise,
+    /// edge detection wakes the calling thread when the channel changes, so it
+    /// does not spin like a loop around `input`. The channel must not have edge
+    /// detection enabled with `add_event_detect`. The mock backend has no edge
+    /// detection and reads the channel every poll interval instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The input channel to watch.
+    /// * `level` - The level to wait for.
+    /// * `timeout` - An optional maximum time to wait. If `None`, waits indefinitely.
+    ///
+    /// Returns `true` if the level was reached, or `false` if the timeout expired first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    /// gpio.setup(vec![18], Direction::IN, None, None).unwrap();
+    ///
+    /// if !gpio.wait_for_level(18, Level::HIGH, Some(Duration::from_secs(5))).unwrap() {
+    ///     println!("The enable line did not go high");
+    /// }
+    /// ```
+    pub fn wait_for_level<C: Into<Channel>>(
+        &self,
+        channel: C,
+        level: Level,
+        timeout: Option<Duration>,
+    ) -> Result<bool, Error> {
+        self.wait_for_level_with_interval(channel, level, timeout, self.poll_interval)
+    }
 
-        // // check if pullup/down value is specified and/or valid
-        // if pull_up_down_explicit:
-        //     warnings.warn("Jetson.GPIO ignores setup()'s pull_up_down parameter")
-        // if (pull_up_down != PUD_OFF and pull_up_down != PUD_UP and
-        //         pull_up_down != PUD_DOWN):
-        //     raise ValueError("Invalid value for pull_up_down; should be one of"
-        //                      "PUD_OFF, PUD_UP or PUD_DOWN")
+    /// Waits until an input channel is at the given level, polling at the given interval.
+    ///
+    /// Same as `wait_for_level`, but the mock backend reads the channel every
+    /// `interval` instead of the poll interval set with `set_poll_interval`. The
+    /// other backends wait for edges and don't poll, so the interval has no
+    /// effect there.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The input channel to watch.
+    /// * `level` - The level to wait for.
+    /// * `timeout` - An optional maximum time to wait. If `None`, waits indefinitely.
+    /// * `interval` - The time to sleep between two reads of the channel.
+    ///
+    /// Returns `true` if the level was reached, or `false` if the timeout expired first.
+    pub fn wait_for_level_with_interval<C: Into<Channel>>(
+        &self,
+        channel: C,
+        level: Level,
+        timeout: Option<Duration>,
+        interval: Duration,
+    ) -> Result<bool, Error> {
+        let channel = channel.into();
+        let reached = || Ok(self.input(channel.clone())? == level);
 
-        if self.gpio_warnings {
-            for ch_info in ch_infos.clone() {
-                let sysfs_cfg = sysfs_channel_configuration(ch_info.clone());
-                let app_cfg = self.app_channel_configuration(ch_info);
+        if self.backend == Backend::MOCK {
+            let ch_info = self.channel_to_info(&channel, true, false)?;
+            self.check_input(&ch_info)?;
 
-                // warn if channel has been setup external to current program
-                if app_cfg.is_none() && sysfs_cfg.is_some() {
-                    println!("This channel is already in use, continuing anyway. Use GPIO.setwarnings(False) to disable warnings");
+            let start = Instant::now();
+            loop {
+                if reached()? {
+                    return Ok(true);
                 }
+                if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                    return Ok(false);
+                }
+                thread::sleep(interval);
             }
         }
 
-        // cleanup if the channel is already setup
-        for ch_info in ch_infos.clone() {
-            // if ch_info.channel in channel_configuration:
-            //     cleanup_one(ch_info)
-            if self.channel_configuration.contains_key(&ch_info.channel) {
-                self.cleanup_one(ch_info);
+        let edge = match self.electrical_level(&channel, level.clone()) {
+            Level::HIGH => Edge::RISING,
+            Level::LOW => Edge::FALLING,
+        };
+        let (ch_info, line) = self.edge_wait_target(channel.clone(), edge)?;
+        blocking_wait_for_level(&ch_info, edge, timeout, line, reached)
+    }
+
+    /// Counts the edges detected on a channel during the given time.
+    ///
+    /// The channel is set up as an input first if it is not set up yet. Blocks
+    /// for `duration`, e.g. to read a fan tachometer or a flow sensor. The
+    /// channel must not have edge detection enabled with `add_event_detect`.
+    ///
+    /// Every edge wakes the calling thread, so pulses that follow each other too
+    /// closely are missed. With the sysfs backend, which only signals that an
+    /// edge occurred, counts are reliable up to about 1 kHz. The character device
+    /// backend queues the edges in the kernel and keeps up with a few 10 kHz.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to count the pulses of.
+    /// * `edge` - The edges to count. `Edge::BOTH` counts two edges per pulse.
+    /// * `duration` - How long to count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, Edge, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let pulses = gpio.count_pulses(18, Edge::RISING, Duration::from_secs(1)).unwrap();
+    /// println!("{} pulses per second", pulses);
+    /// ```
+    pub fn count_pulses<C: Into<Channel>>(&mut self, channel: C, edge: Edge, duration: Duration) -> Result<u64, Error> {
+        let channel = channel.into();
+        let ch_info = self.channel_to_info(&channel, true, false)?;
+        match self.app_channel_configuration(ch_info.clone()) {
+            None => {
+                self.setup(vec![channel.clone()], Direction::IN, None, None)?;
             }
+            Some(_) => self.check_input(&ch_info)?,
         }
 
-        match direction {
-            Direction::OUT => {
-                for ch_info in ch_infos.clone() {
-                    self.setup_single_out(ch_info, initial.clone());
-                }
-            }
-            _ => {
-                if initial.is_some() {
-                    return Err(Error::msg("initial parameter is not valid for inputs"));
-                }
-                for ch_info in ch_infos {
-                    self.setup_single_in(ch_info);
-                }
-            }
+        let (ch_info, line) = self.edge_wait_target(channel, edge)?;
+        blocking_count_edges(&ch_info, edge, duration, line)
+    }
+
+    /// Measures the frequency of the pulses on a channel in Hz.
+    ///
+    /// Counts the edges with `count_pulses` for `duration` and divides the count
+    /// by it. Longer durations give more accurate results for slow signals.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to measure.
+    /// * `edge` - The edges to count, usually `Edge::RISING`.
+    /// * `duration` - How long to count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use jetson_gpio::{GPIO, Edge, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// // a fan tachometer pulses twice per revolution
+    /// let hz = gpio.measure_frequency(18, Edge::RISING, Duration::from_secs(2)).unwrap();
+    /// println!("{} RPM", hz * 60.0 / 2.0);
+    /// ```
+    pub fn measure_frequency<C: Into<Channel>>(
+        &mut self,
+        channel: C,
+        edge: Edge,
+        duration: Duration,
+    ) -> Result<f64, Error> {
+        if duration.is_zero() {
+            return Err(Error::msg("The measurement duration must not be zero"));
         }
 
-        Ok(())
+        let count = self.count_pulses(channel, edge, duration)?;
+        Ok(count as f64 / duration.as_secs_f64())
     }
 
-    /// Cleans up channels at the end of the program.
+    /// Waits for an edge on an input channel without blocking the thread.
+    ///
+    /// Available with the `tokio` feature. The returned future must run on a tokio
+    /// runtime with I/O enabled, and completes with the detected transition, like
+    /// `wait_for_edge`. Use `tokio::time::timeout` to give up after some time.
+    /// Edge detection is disabled again when the future completes or is dropped.
     ///
     /// # Arguments
     ///
-    /// * `channels` - An optional list of channels to cleanup. If no channel is provided, all channels are cleaned.
-    pub fn cleanup(&mut self, channels: Option<Vec<u32>>) -> Result<(), Error> {
-        // warn if no channel is setup
-        if self.gpio_mode.is_none() {
-            if self.gpio_warnings {
-                println!("No channels have been set up yet - nothing to clean up! Try cleaning up at the end of your program instead!");
+    /// * `channel` - The input channel to watch.
+    /// * `edge` - The edge to wait for.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Edge};
+    ///
+    /// // input 18 is set up with `setup`
+    /// async fn wait_for_button(gpio: &GPIO) {
+    ///     match gpio.wait_for_edge_async(18, Edge::FALLING).await {
+    ///         Ok(_) => println!("Button pressed"),
+    ///         Err(e) => eprintln!("Failed to wait for the button: {}", e),
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_edge_async<C: Into<Channel>>(&self, channel: C, edge: Edge) -> Result<Edge, Error> {
+        let (ch_info, line) = self.edge_wait_target(channel.into(), edge)?;
+        async_wait_for_edge(&ch_info, edge, line).await
+    }
+
+    /// Returns a stream of the edges detected on an input channel.
+    ///
+    /// Available with the `tokio` feature, and must be called on a tokio runtime
+    /// with I/O enabled. Every detected transition is yielded as an `EdgeEvent`
+    /// with its time, e.g. to decode a rotary encoder or count the pulses of a
+    /// flow meter. Edge detection stays enabled until the stream is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The input channel to watch.
+    /// * `edge` - The edges to report.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures_core::Stream;
+    /// use jetson_gpio::{GPIO, Edge, EdgeEvent};
+    ///
+    /// // input 18 is set up with `setup`
+    /// fn pulses(gpio: &GPIO) -> impl Stream<Item = EdgeEvent> + '_ {
+    ///     gpio.edge_stream(18, Edge::RISING).unwrap()
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn edge_stream<C: Into<Channel>>(&self, channel: C, edge: Edge) -> Result<EdgeStream<'_>, Error> {
+        let (ch_info, line) = self.edge_wait_target(channel.into(), edge)?;
+        EdgeStream::start(&ch_info, edge, line)
+    }
+
+    // Checks that edges can be waited for on the channel, and returns it with its
+    // requested line on the character device backend.
+    fn edge_wait_target(&self, channel: Channel, edge: Edge) -> Result<(ChannelInfo, Option<&Arc<LineRequest>>), Error> {
+        let ch_info = self.channel_to_info(&channel, true, false)?;
+        self.check_input(&ch_info)?;
+
+        if let Some(detector) = self.events.get(&ch_info.channel) {
+            if detector.edge() != edge {
+                return Err(Error::msg("Conflicting edge detection already enabled for this GPIO channel"));
             }
-            return Ok(());
+            return Err(Error::msg("Edge detection is already enabled for this GPIO channel, use event_detected or a callback instead"));
         }
 
-        // clean all channels if no channel param provided
-        if channels.is_none() {
-            self.cleanup_all()?;
-            return Ok(());
+        let line = match self.backend {
+            Backend::SYSFS => None,
+            Backend::CHARDEV => Some(&self.requested_line(&ch_info)?.file),
+            Backend::MOCK => return Err(unsupported_by_mock("Edge detection")),
+        };
+
+        Ok((ch_info, line))
+    }
+
+    /// Creates a hardware PWM output on a channel.
+    ///
+    /// The channel must be PWM capable. If it was set up as a GPIO, it is
+    /// cleaned up first so the PWM can drive the pin. Frequencies above the
+    /// maximum of the PWM controller are handled as configured with
+    /// `set_pwm_frequency_limit`. The PWM is unexported when the channel is
+    /// cleaned up. The mock backend has no PWM controllers and returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to drive.
+    /// * `frequency_hz` - The frequency of the PWM output in Hz.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let mut pwm = gpio.pwm(33, 50.0).unwrap();
+    /// pwm.start(25.0).unwrap();
+    /// ```
+    pub fn pwm<C: Into<Channel>>(&mut self, channel: C, frequency_hz: f64) -> Result<PWM, Error> {
+        let channel = channel.into();
+        if self.backend == Backend::MOCK {
+            self.channel_to_info(&channel, false, false)?;
+            return Err(unsupported_by_mock("Hardware PWM"));
         }
+        let ch_info = self.channel_to_info(&channel, false, true)?;
 
-        let ch_infos = self.channels_to_infos(channels.unwrap(), false, false)?;
-        for ch_info in ch_infos {
-            if self.channel_configuration.contains_key(&ch_info.channel) {
-                self.cleanup_one(ch_info);
+        match self.app_channel_configuration(ch_info.clone()) {
+            Some(Direction::HARD_PWM) => {
+                return Err(Error::msg("Can't create duplicate PWM objects"));
+            }
+            // apps typically set up channels as GPIO before making them PWM, the
+            // GPIO export must be undone to allow the PWM to run on the pin
+            Some(_) => self.cleanup_one(ch_info.clone())?,
+            None => {
+                // warn if channel has been setup external to current program
+                if self.gpio_warnings && sysfs_channel_configuration(ch_info.clone()).is_some() {
+                    self.warn_in_use(&ch_info.channel);
+                }
             }
         }
 
-        Ok(())
+        let pwm = PWM::new(
+            ch_info.clone(),
+            frequency_hz,
+            pwm_max_frequency(&self.model),
+            self.pwm_frequency_limit,
+            self.gpio_warnings.then(|| self.warning_handler.clone()),
+            self.export_timeout,
+        )?;
+        self.channel_configuration
+            .insert(ch_info.channel, Direction::HARD_PWM);
+
+        Ok(pwm)
     }
 
-    /// Returns the current value of the specified channel.
+    /// Sets up a channel driving an LED that can be switched on and off, see `Led`.
     ///
-    /// Return either `Level::HIGH` or `Level::LOW`.
+    /// The LED starts switched off.
     ///
     /// # Arguments
     ///
-    /// * `channel` - The channel to read from.
-    pub fn input(&self, channel: u32) -> Result<Level, Error> {
-        let ch_info = self.channel_to_info(channel, true, false)?;
+    /// * `channel` - The channel the LED is connected to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let mut led = gpio.led(7).unwrap();
+    /// led.on().unwrap();
+    /// ```
+    pub fn led<C: Into<Channel>>(&mut self, channel: C) -> Result<Led, Error> {
+        Ok(Led::with_pin(self.setup_output_pin(channel, Some(Level::LOW))?))
+    }
 
-        let app_cfg = self.app_channel_configuration(ch_info.clone());
-        if app_cfg.is_none() || ![Direction::IN, Direction::OUT].contains(&app_cfg.unwrap()) {
-            return Err(Error::msg("You must setup() the GPIO channel first"));
+    /// Sets up a channel driving an LED whose brightness can be set, see `Led`.
+    ///
+    /// If the channel has a hardware PWM, the brightness is set with its duty
+    /// cycle. Otherwise, the LED can only be switched on and off, like one
+    /// created with `led`. The LED starts switched off.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel the LED is connected to.
+    /// * `frequency_hz` - The PWM frequency, high enough to avoid visible flicker, e.g. 1000.0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let mut led = gpio.led_pwm(33, 1000.0).unwrap();
+    /// led.set_brightness(0.5).unwrap();
+    /// ```
+    pub fn led_pwm<C: Into<Channel>>(&mut self, channel: C, frequency_hz: f64) -> Result<Led, Error> {
+        let channel = channel.into();
+        let ch_info = self.channel_to_info(&channel, false, false)?;
+        if ch_info.pwm_chip_dir.is_none() {
+            return self.led(channel);
         }
 
-        match read_value(ch_info).as_str() {
-            "0" => Ok(Level::LOW),
-            _ => Ok(Level::HIGH),
-        }
+        let pwm = self.pwm(channel.clone(), frequency_hz)?;
+        Ok(Led::with_pwm(channel, pwm))
     }
 
-    /// Writes a value to channels.
+    /// Sets up a hardware PWM channel driving a hobby servo, see `Servo`.
+    ///
+    /// Returns an error if the channel has no hardware PWM or if the pulses of
+    /// the configuration do not fit in its period. The servo is not driven
+    /// until its angle is set.
     ///
     /// # Arguments
     ///
-    /// * `channels` - A list of channels to write to.
-    /// * `values` - A list of values to write to the channels. Must be either HIGH or LOW.
+    /// * `channel` - The PWM channel the servo signal is connected to.
+    /// * `config` - The pulse widths and frequency of the servo, `ServoConfig::default()` for most servos.
     ///
     /// # Example
+    ///
     /// ```rust
-    /// use jetson_gpio::{GPIO, Direction, Level, Mode};
+    /// use jetson_gpio::{GPIO, Mode, ServoConfig};
     ///
     /// let mut gpio = GPIO::new();
     /// gpio.setmode(Mode::BOARD).unwrap();
-    /// gpio.setup(vec![7], Direction::OUT, None).unwrap();
-    /// gpio.output(vec![7], vec![Level::HIGH]).unwrap();
+    ///
+    /// let mut servo = gpio.servo(33, ServoConfig::default()).unwrap();
+    /// servo.set_angle(45.0).unwrap();
     /// ```
-    pub fn output(&self, channels: Vec<u32>, values: Vec<Level>) -> Result<(), Error> {
-        let ch_infos = self.channels_to_infos(channels, true, false)?;
+    pub fn servo<C: Into<Channel>>(&mut self, channel: C, config: ServoConfig) -> Result<Servo, Error> {
+        config.check()?;
+        let pwm = self.pwm(channel, config.frequency_hz)?;
+        Ok(Servo::new(pwm, config))
+    }
+}
 
-        if values.len() != ch_infos.len() {
-            return Err(Error::msg("Number of values != number of channels"));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // check that channels have been set as output
-        for ch_info in ch_infos.clone() {
-            let app_cfg = self.app_channel_configuration(ch_info);
-            if app_cfg.is_none() || app_cfg.unwrap() != Direction::OUT {
-                return Err(Error::msg("The GPIO channel has not been set up as an OUTPUT"));
+    #[test]
+    fn exports_are_retried_until_the_policy_is_exhausted() {
+        let ch_info = channel_info(7, "/sys/devices/2200000.gpio");
+        let retry = RetryPolicy {
+            retries: 2,
+            backoff: Duration::ZERO,
+        };
+
+        let mut attempts = 0;
+        let result = with_retries(&ch_info, false, retry, || {
+            attempts += 1;
+            match attempts {
+                1 => Err(Error::msg("not settled yet")),
+                _ => Ok(()),
             }
+        });
+        assert!(result.is_ok());
+        assert_eq!(attempts, 2);
+
+        let err = with_retries(&ch_info, true, retry, || Err(Error::msg("not settled yet"))).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<GpioError>(),
+            Some(GpioError::EXPORT_FAILED {
+                unexport: true,
+                attempts: 3,
+                ..
+            })
+        ));
+
+        let mut attempts = 0;
+        let err = with_retries(&ch_info, false, retry, || {
+            attempts += 1;
+            Err(Error::new(GpioError::CHANNEL_BUSY(ch_info.channel.clone())))
+        })
+        .unwrap_err();
+        assert_eq!(err.downcast_ref::<GpioError>(), Some(&GpioError::CHANNEL_BUSY(Channel::Number(7))));
+        assert_eq!(attempts, 1);
+    }
+
+    fn channel_info(channel: u32, gpio_chip_dir: &str) -> ChannelInfo {
+        ChannelInfo {
+            channel: Channel::Number(channel),
+            gpio_chip_dir: String::from(gpio_chip_dir),
+            gpio_chip_dev: Some(String::from("/dev/gpiochip0")),
+            line_offset: 106,
+            global_gpio: 454,
+            global_gpio_name: String::from("PQ.06"),
+            pwm_chip_dir: None,
+            pwm_id: None,
         }
+    }
+
+    fn test_gpio(channels: Vec<ChannelInfo>) -> GPIO {
+        let board_data: HashMap<Channel, ChannelInfo> =
+            channels.into_iter().map(|c| (c.channel.clone(), c)).collect();
+
+        let jetson_info = JetsonInfo {
+            p1_revision: 1,
+            ram: String::from("32768M"),
+            revision: String::from("Unknown"),
+            ttype: String::from("JETSON_ORIN"),
+            manufacturer: String::from("NVIDIA"),
+            processor: String::from("A78AE"),
+            serial_number: None,
+        };
+        let pin_aliases = vec![PinAliases {
+            board: 7,
+            bcm: Some(4),
+            cvm: String::from("MCLK05"),
+            tegra_soc: String::from("GP66"),
+        }];
+
+        GPIO::from_data(
+            (
+                String::from("JETSON_ORIN"),
+                jetson_info,
+                HashMap::from([(Mode::BOARD, board_data)]),
+                vec![(String::from("2200000.gpio"), 348, 164)],
+                pin_aliases,
+            ),
+            Backend::SYSFS,
+        )
+    }
+
+    #[test]
+    fn in_use_warning_is_printed_once_per_channel() {
+        let mut gpio = test_gpio(vec![]);
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink = warnings.clone();
+        gpio.set_warning_handler(move |message| sink.lock().unwrap().push(message.to_string()));
+
+        gpio.warn_in_use(&Channel::Number(7));
+        gpio.warn_in_use(&Channel::Number(7));
+        gpio.warn_in_use(&Channel::Number(11));
+        gpio.setwarnings(false);
+        gpio.warn_in_use(&Channel::Number(13));
+
+        let warnings = warnings.lock().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("Channel 7 is already in use"));
+        assert!(warnings[1].starts_with("Channel 11 is already in use"));
+    }
+
+    #[test]
+    fn chip_info_lists_the_resolved_chips() {
+        let gpio = test_gpio(vec![channel_info(7, "/sys/devices/platform/2200000.gpio")]);
+        assert_eq!(gpio.chip_info(), vec![(String::from("2200000.gpio"), 348, 164)]);
+    }
+
+    #[test]
+    fn pwm_channels_have_a_pwm_controller() {
+        let mut pwm_channel = channel_info(33, "/sys/devices/platform/2200000.gpio");
+        pwm_channel.pwm_chip_dir = Some(String::from("/sys/devices/platform/32c0000.pwm/pwm/pwmchip2"));
+        pwm_channel.pwm_id = Some(0);
+        let mut gpio = test_gpio(vec![channel_info(7, "/sys/devices/platform/2200000.gpio"), pwm_channel]);
+        assert!(gpio.pwm_channels().is_err());
+
+        gpio.setmode(Mode::BOARD).unwrap();
+        assert_eq!(gpio.pwm_channels().unwrap(), vec![Channel::Number(33)]);
+    }
+
+    #[test]
+    fn unresolved_chip_is_not_a_gpio() {
+        let mut gpio = test_gpio(vec![
+            channel_info(7, "/sys/devices/platform/2200000.gpio"),
+            channel_info(11, ""),
+        ]);
+        gpio.setmode(Mode::BOARD).unwrap();
+
+        assert!(gpio.channels_to_infos(vec![Channel::Number(7)], true, false).is_ok());
+        let err = gpio
+            .channels_to_infos(vec![Channel::Number(7), Channel::Number(11)], true, false)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Channel 11 is not a GPIO");
+        assert_eq!(err.downcast_ref::<GpioError>(), Some(&GpioError::NOT_A_GPIO(Channel::Number(11))));
+    }
+
+    #[test]
+    fn cvm_channels_are_looked_up_by_name() {
+        let mut gpio = test_gpio(vec![channel_info(7, "/sys/devices/platform/2200000.gpio")]);
+        let mut ch_info = channel_info(7, "/sys/devices/platform/2200000.gpio");
+        ch_info.channel = Channel::from("MCLK05");
+        gpio.channel_data_by_mode
+            .insert(Mode::CVM, HashMap::from([(ch_info.channel.clone(), ch_info)]));
+        gpio.setmode(Mode::CVM).unwrap();
+        assert_eq!(gpio.mode(), Some(Mode::CVM));
+
+        assert!(gpio.channels_to_infos(vec![Channel::from("MCLK05")], true, false).is_ok());
+        assert_eq!(gpio.pin_aliases("MCLK05").unwrap().board, 7);
+        let err = gpio.channels_to_infos(vec![Channel::from(7)], true, false).unwrap_err();
+        assert_eq!(err.to_string(), "The channel sent is invalid: 7");
+    }
+
+    #[test]
+    fn channels_are_translated_between_modes() {
+        let mut gpio = test_gpio(vec![channel_info(7, "/sys/devices/platform/2200000.gpio")]);
+        assert_eq!(gpio.translate(7, Mode::BOARD, Mode::BCM).unwrap(), Channel::Number(4));
+        assert_eq!(gpio.translate(4, Mode::BCM, Mode::TEGRA_SOC).unwrap(), Channel::from("GP66"));
+        assert_eq!(gpio.translate("MCLK05", Mode::CVM, Mode::BOARD).unwrap(), Channel::Number(7));
+
+        let err = gpio.translate(4, Mode::BOARD, Mode::BCM).unwrap_err();
+        assert_eq!(err.downcast_ref::<GpioError>(), Some(&GpioError::INVALID_CHANNEL(Channel::Number(4))));
+
+        gpio.pin_aliases[0].bcm = None;
+        let err = gpio.translate(7, Mode::BOARD, Mode::BCM).unwrap_err();
+        assert_eq!(err.to_string(), "Channel 7 has no BCM number");
+    }
 
-        for (ch_info, value) in ch_infos.iter().zip(values.iter()) {
-            output_one(ch_info.clone(), value.clone());
+    #[test]
+    fn shared_gpio_is_usable_from_other_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GPIO>();
+        assert_send_sync::<SharedGpio>();
+
+        let gpio = test_gpio(vec![channel_info(7, "/sys/devices/platform/2200000.gpio")]).shared();
+        gpio.write().setmode(Mode::BOARD).unwrap();
+
+        let handle = gpio.clone();
+        let mode = std::thread::spawn(move || handle.read().getmode()).join().unwrap();
+        assert_eq!(mode.as_deref(), Some("BOARD"));
+    }
+
+    #[test]
+    fn level_converts_to_and_from_bool() {
+        assert_eq!(Level::from(true), Level::HIGH);
+        assert_eq!(Level::from(false), Level::LOW);
+        assert!(bool::from(Level::HIGH));
+        assert_eq!(Level::LOW.to_string(), "LOW");
+    }
+
+    #[test]
+    fn levels_are_written_to_sysfs_as_digits() {
+        assert_eq!(Level::HIGH.sysfs_str(), "1");
+        assert_eq!(Level::LOW.sysfs_str(), "0");
+        assert_eq!(Level::HIGH.inverted().sysfs_str(), "0");
+    }
+
+    #[test]
+    fn output_direction_sets_initial_level_with_direction() {
+        assert_eq!(output_direction(Some(Level::HIGH)), "high");
+        assert_eq!(output_direction(Some(Level::LOW)), "low");
+        assert_eq!(output_direction(None), "out");
+    }
+
+    #[test]
+    fn direction_and_level_round_trip_through_strings() {
+        for direction in [Direction::UNKNOWN, Direction::OUT, Direction::IN, Direction::TRISTATE, Direction::HARD_PWM] {
+            assert_eq!(Direction::from_str(direction.to_str()).unwrap(), direction);
         }
+        for level in [Level::LOW, Level::HIGH] {
+            assert_eq!(Level::from_str(level.to_str()).unwrap(), level);
+        }
+        assert!(Direction::from_str("out").is_err());
+        assert!(Level::from_str("1").is_err());
+    }
 
-        Ok(())
+    #[test]
+    fn pin_table_lists_pins_before_setmode() {
+        let gpio = test_gpio(Vec::new());
+        assert_eq!(
+            gpio.pin_table(),
+            vec![PinMapEntry {
+                board: 7,
+                bcm: Some(4),
+                cvm: String::from("MCLK05"),
+                tegra_soc: String::from("GP66"),
+                pwm_capable: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn platform_summary_names_the_model() {
+        let gpio = test_gpio(Vec::new());
+        assert!(gpio.is_model("JETSON_ORIN"));
+        assert!(!gpio.is_model("JETSON_NANO"));
+
+        let summary = gpio.platform_summary();
+        assert!(summary.starts_with("Model: JETSON_ORIN\n"));
+        assert!(summary.contains("Processor: A78AE\n"));
     }
 }
@@ -0,0 +1,146 @@
+use anyhow::Error;
+use std::sync::Arc;
+
+use crate::gpio::{effective_backend, print_warning, Backend, RetryPolicy, WarningHandler, GPIO};
+use crate::gpio_pin_data::{get_data, get_data_for_model, get_mock_data, Mode};
+
+/// Creates a `GPIO` object with its settings applied up front, created with `GPIO::builder`.
+///
+/// Unlike `GPIO::new`, `build` returns an error instead of panicking, and sets
+/// the pin numbering mode right away, so no separate `setmode` call is needed.
+///
+/// * `mode` - The pin numbering mode, not set by default
+/// * `warnings` - Whether warnings are enabled, `true` by default
+/// * `backend` - The kernel interface, `Backend::SYSFS` by default
+/// * `model_override` - The Jetson model to use instead of detecting it, see `GPIO::new_for_model`
+/// * `export_retry` - The retries of failed sysfs exports, `RetryPolicy::default()` by default
+/// * `warning_handler` - The function that receives warnings, printing them to stdout by default
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Backend, Direction, Mode};
+///
+/// let mut gpio = GPIO::builder()
+///     .mode(Mode::BOARD)
+///     .warnings(false)
+///     .backend(Backend::CHARDEV)
+///     .build()
+///     .unwrap();
+/// gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+/// ```
+pub struct GpioBuilder {
+    mode: Option<Mode>,
+    warnings: bool,
+    backend: Backend,
+    model: Option<String>,
+    export_retry: RetryPolicy,
+    warning_handler: WarningHandler,
+}
+
+impl GpioBuilder {
+    pub(crate) fn new() -> GpioBuilder {
+        GpioBuilder {
+            mode: None,
+            warnings: true,
+            backend: Backend::SYSFS,
+            model: None,
+            export_retry: RetryPolicy::default(),
+            warning_handler: Arc::new(print_warning),
+        }
+    }
+
+    /// Sets the pin numbering mode of the created object.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The pin numbering mode, see `GPIO::setmode`
+    pub fn mode(mut self, mode: Mode) -> GpioBuilder {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Enables or disables warnings, see `GPIO::setwarnings`.
+    ///
+    /// # Arguments
+    ///
+    /// * `warnings` - `true` to enable warnings, `false` to disable warnings
+    pub fn warnings(mut self, warnings: bool) -> GpioBuilder {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Sets the kernel interface used to access the GPIOs.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - `Backend::SYSFS`, `Backend::CHARDEV` or `Backend::MOCK`
+    pub fn backend(mut self, backend: Backend) -> GpioBuilder {
+        self.backend = backend;
+        self
+    }
+
+    /// Uses the given Jetson model instead of detecting it from the device tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model name, e.g. `"JETSON_NANO"` or `"JETSON_ORIN_NX"`
+    pub fn model_override(mut self, model: &str) -> GpioBuilder {
+        self.model = Some(String::from(model));
+        self
+    }
+
+    /// Sets how often a failed sysfs export or unexport is retried, see `GPIO::set_export_retry`.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of retries and the delay between them
+    pub fn export_retry(mut self, retry: RetryPolicy) -> GpioBuilder {
+        self.export_retry = retry;
+        self
+    }
+
+    /// Sets the function that receives warnings, see `GPIO::set_warning_handler`.
+    ///
+    /// Unlike `GPIO::set_warning_handler`, the handler also receives the warnings
+    /// raised while `build` detects the board.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The function called with the message of every warning
+    pub fn warning_handler<F: Fn(&str) + Send + Sync + 'static>(mut self, handler: F) -> GpioBuilder {
+        self.warning_handler = Arc::new(handler);
+        self
+    }
+
+    /// Creates the `GPIO` object.
+    ///
+    /// Returns an error if the board cannot be identified, the model override is
+    /// not a valid model, or the pin numbering mode cannot be set.
+    pub fn build(self) -> Result<GPIO, Error> {
+        let backend = effective_backend(self.backend);
+        let handler = self.warning_handler.clone();
+        let warnings = self.warnings;
+        let warn = move |message: &str| {
+            if warnings {
+                handler(message);
+            }
+        };
+        let data = match (backend, self.model.as_deref()) {
+            (Backend::MOCK, model) => get_mock_data(model, &warn)?,
+            (_, Some(model)) => get_data_for_model(model, &warn)?,
+            (_, None) => get_data(&warn)?,
+        };
+
+        let mut gpio = GPIO::from_data(data, backend);
+        let handler = self.warning_handler;
+        gpio.set_warning_handler(move |message| handler(message));
+        gpio.setwarnings(self.warnings);
+        gpio.set_export_retry(self.export_retry);
+        if let Some(mode) = self.mode {
+            gpio.setmode(mode)?;
+        }
+
+        Ok(gpio)
+    }
+}
@@ -0,0 +1,588 @@
+// GPIO character device (/dev/gpiochipN) access through the v2 uAPI of
+// linux/gpio.h. The structs mirror the kernel layout and must not be reordered.
+
+use anyhow::Error;
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    mem,
+    ops::Deref,
+    os::unix::io::{AsRawFd, FromRawFd},
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::gpio::{Direction, Drive, GpioError, Level, Pull};
+use crate::gpio_event::Edge;
+use crate::gpio_paths::paths;
+use crate::gpio_pin_data::ChannelInfo;
+
+const GPIO_MAX_NAME_SIZE: usize = 32;
+const GPIO_V2_LINES_MAX: usize = 64;
+const GPIO_V2_LINE_NUM_ATTRS_MAX: usize = 10;
+
+const GPIO_V2_LINE_FLAG_USED: u64 = 1 << 0;
+const GPIO_V2_LINE_FLAG_INPUT: u64 = 1 << 2;
+const GPIO_V2_LINE_FLAG_OUTPUT: u64 = 1 << 3;
+const GPIO_V2_LINE_FLAG_EDGE_RISING: u64 = 1 << 4;
+const GPIO_V2_LINE_FLAG_EDGE_FALLING: u64 = 1 << 5;
+const GPIO_V2_LINE_FLAG_OPEN_DRAIN: u64 = 1 << 6;
+const GPIO_V2_LINE_FLAG_BIAS_PULL_UP: u64 = 1 << 8;
+const GPIO_V2_LINE_FLAG_BIAS_PULL_DOWN: u64 = 1 << 9;
+const GPIO_V2_LINE_FLAG_BIAS_DISABLED: u64 = 1 << 10;
+const GPIO_V2_LINE_FLAG_BIAS: u64 =
+    GPIO_V2_LINE_FLAG_BIAS_PULL_UP | GPIO_V2_LINE_FLAG_BIAS_PULL_DOWN | GPIO_V2_LINE_FLAG_BIAS_DISABLED;
+
+const GPIO_V2_LINE_ATTR_ID_FLAGS: u32 = 1;
+const GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES: u32 = 2;
+const GPIO_V2_LINE_ATTR_ID_DEBOUNCE: u32 = 3;
+
+const GPIO_V2_LINE_EVENT_RISING_EDGE: u32 = 1;
+
+// consumer label shown for lines requested by this crate
+const CONSUMER: &[u8] = b"jetson_gpio";
+
+#[repr(C)]
+struct GpioChipInfo {
+    name: [u8; GPIO_MAX_NAME_SIZE],
+    label: [u8; GPIO_MAX_NAME_SIZE],
+    lines: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct GpioV2LineAttribute {
+    id: u32,
+    padding: u32,
+    // union of flags, values and debounce_period_us
+    value: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct GpioV2LineConfigAttribute {
+    attr: GpioV2LineAttribute,
+    mask: u64,
+}
+
+#[repr(C)]
+struct GpioV2LineConfig {
+    flags: u64,
+    num_attrs: u32,
+    padding: [u32; 5],
+    attrs: [GpioV2LineConfigAttribute; GPIO_V2_LINE_NUM_ATTRS_MAX],
+}
+
+#[repr(C)]
+struct GpioV2LineRequest {
+    offsets: [u32; GPIO_V2_LINES_MAX],
+    consumer: [u8; GPIO_MAX_NAME_SIZE],
+    config: GpioV2LineConfig,
+    num_lines: u32,
+    event_buffer_size: u32,
+    padding: [u32; 5],
+    fd: i32,
+}
+
+#[repr(C)]
+struct GpioV2LineValues {
+    bits: u64,
+    mask: u64,
+}
+
+#[repr(C)]
+struct GpioV2LineInfo {
+    name: [u8; GPIO_MAX_NAME_SIZE],
+    consumer: [u8; GPIO_MAX_NAME_SIZE],
+    offset: u32,
+    num_attrs: u32,
+    flags: u64,
+    attrs: [GpioV2LineAttribute; GPIO_V2_LINE_NUM_ATTRS_MAX],
+    padding: [u32; 4],
+}
+
+#[repr(C)]
+struct GpioV2LineEvent {
+    timestamp_ns: u64,
+    id: u32,
+    offset: u32,
+    seqno: u32,
+    line_seqno: u32,
+    padding: [u32; 6],
+}
+
+const fn ioc(dir: u64, nr: u64, size: usize) -> u64 {
+    (dir << 30) | ((size as u64) << 16) | (0xB4 << 8) | nr
+}
+
+const IOC_READ: u64 = 2;
+const IOC_READ_WRITE: u64 = 3;
+
+const GPIO_GET_CHIPINFO_IOCTL: u64 = ioc(IOC_READ, 0x01, mem::size_of::<GpioChipInfo>());
+const GPIO_V2_GET_LINEINFO_IOCTL: u64 = ioc(IOC_READ_WRITE, 0x05, mem::size_of::<GpioV2LineInfo>());
+const GPIO_V2_GET_LINE_IOCTL: u64 = ioc(IOC_READ_WRITE, 0x07, mem::size_of::<GpioV2LineRequest>());
+const GPIO_V2_LINE_SET_CONFIG_IOCTL: u64 = ioc(IOC_READ_WRITE, 0x0D, mem::size_of::<GpioV2LineConfig>());
+const GPIO_V2_LINE_GET_VALUES_IOCTL: u64 = ioc(IOC_READ_WRITE, 0x0E, mem::size_of::<GpioV2LineValues>());
+const GPIO_V2_LINE_SET_VALUES_IOCTL: u64 = ioc(IOC_READ_WRITE, 0x0F, mem::size_of::<GpioV2LineValues>());
+
+fn ioctl<T>(file: &File, request: u64, arg: &mut T) -> io::Result<()> {
+    // SAFETY: `arg` points to a struct matching the layout the request expects
+    let ret = unsafe { libc::ioctl(file.as_raw_fd(), request as _, arg as *mut T) };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+fn zeroed<T>() -> T {
+    // SAFETY: only used for the plain-data uAPI structs above, for which all zeroes is valid
+    unsafe { mem::zeroed() }
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
+
+fn line_config(direction: Direction, pull: Pull, initial: Option<Level>, edge: Option<Edge>) -> GpioV2LineConfig {
+    let mut config: GpioV2LineConfig = zeroed();
+
+    config.flags = match direction {
+        Direction::OUT => GPIO_V2_LINE_FLAG_OUTPUT,
+        _ => GPIO_V2_LINE_FLAG_INPUT,
+    };
+    config.flags |= match pull {
+        Pull::UP => GPIO_V2_LINE_FLAG_BIAS_PULL_UP,
+        Pull::DOWN => GPIO_V2_LINE_FLAG_BIAS_PULL_DOWN,
+        Pull::OFF => 0,
+    };
+    config.flags |= match edge {
+        Some(Edge::RISING) => GPIO_V2_LINE_FLAG_EDGE_RISING,
+        Some(Edge::FALLING) => GPIO_V2_LINE_FLAG_EDGE_FALLING,
+        Some(Edge::BOTH) => GPIO_V2_LINE_FLAG_EDGE_RISING | GPIO_V2_LINE_FLAG_EDGE_FALLING,
+        None => 0,
+    };
+
+    if initial.is_some() {
+        set_config_output_values(&mut config, &[initial]);
+    }
+
+    config
+}
+
+// Adds the initial values of the output lines of a request to a line config,
+// in the order of the request's lines. Lines without a value start low.
+fn set_config_output_values(config: &mut GpioV2LineConfig, initials: &[Option<Level>]) {
+    let attr = &mut config.attrs[config.num_attrs as usize];
+    attr.attr.id = GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES;
+    for (index, initial) in initials.iter().enumerate() {
+        if let Some(initial) = initial {
+            attr.mask |= 1 << index;
+            if *initial == Level::HIGH {
+                attr.attr.value |= 1 << index;
+            }
+        }
+    }
+    config.num_attrs += 1;
+}
+
+/// Finds the character device of a GPIO chip, e.g. `/dev/gpiochip0`.
+pub(crate) fn find_gpio_chip_dev(gpio_chip_dir: &str) -> Option<String> {
+    for entry in fs::read_dir(gpio_chip_dir).ok()? {
+        let file_name = entry.ok()?.file_name();
+        let file_name = file_name.to_str()?;
+        if file_name.starts_with("gpiochip") {
+            return Some(format!("{}/{}", paths().dev, file_name));
+        }
+    }
+
+    None
+}
+
+/// Returns the number of lines of a GPIO chip.
+pub(crate) fn chip_lines(gpio_chip_dev: &str) -> Result<u32, Error> {
+    let chip = File::open(gpio_chip_dev)?;
+    let mut info: GpioChipInfo = zeroed();
+    ioctl(&chip, GPIO_GET_CHIPINFO_IOCTL, &mut info)?;
+
+    Ok(info.lines)
+}
+
+fn gpio_chip_dev(ch_info: &ChannelInfo) -> Result<&str, Error> {
+    match ch_info.gpio_chip_dev.as_deref() {
+        Some(gpio_chip_dev) => Ok(gpio_chip_dev),
+        None => Err(Error::msg(format!(
+            "Channel {} has no GPIO character device",
+            ch_info.channel
+        ))),
+    }
+}
+
+fn line_info(ch_info: &ChannelInfo) -> Result<GpioV2LineInfo, Error> {
+    let chip = File::open(gpio_chip_dev(ch_info)?)?;
+    let mut info: GpioV2LineInfo = zeroed();
+    info.offset = ch_info.line_offset;
+    ioctl(&chip, GPIO_V2_GET_LINEINFO_IOCTL, &mut info)?;
+
+    Ok(info)
+}
+
+/// Returns the consumer of a line if it is in use, e.g. by the kernel or another process.
+fn line_consumer(ch_info: &ChannelInfo) -> Result<Option<String>, Error> {
+    let info = line_info(ch_info)?;
+
+    if info.flags & GPIO_V2_LINE_FLAG_USED == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(c_string(&info.consumer)))
+}
+
+/// A request of one or more lines of the same chip.
+///
+/// Derefs to the file descriptor of the request. The lines stay requested
+/// until it is closed, even those that are no longer used.
+pub(crate) struct LineRequest {
+    file: File,
+    // the channels of the requested lines, in the order of the request
+    ch_infos: Vec<ChannelInfo>,
+}
+
+impl LineRequest {
+    /// Returns the position of the line of a channel in the request, if it is part of it.
+    pub(crate) fn index_of(&self, ch_info: &ChannelInfo) -> Option<u32> {
+        self.ch_infos
+            .iter()
+            .position(|other| other.gpio_chip_dev == ch_info.gpio_chip_dev && other.line_offset == ch_info.line_offset)
+            .map(|index| index as u32)
+    }
+}
+
+impl Deref for LineRequest {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        &self.file
+    }
+}
+
+/// A line of a channel, requested on its own or together with other lines of the same chip.
+///
+/// `index` is the position of the line in the request, which addresses it in
+/// the value bitmaps. The request is released once every clone of `file` is dropped.
+pub(crate) struct RequestedLine {
+    pub(crate) file: Arc<LineRequest>,
+    pub(crate) index: u32,
+}
+
+/// Requests the lines of several channels of the same chip as one request.
+/// The lines are indexed in the order of `ch_infos`. `drive` only applies to output lines.
+pub(crate) fn request_lines(
+    ch_infos: &[ChannelInfo],
+    direction: Direction,
+    pull: Pull,
+    initials: &[Option<Level>],
+    drive: Drive,
+) -> Result<LineRequest, Error> {
+    let chip = File::open(gpio_chip_dev(&ch_infos[0])?)?;
+
+    let mut request: GpioV2LineRequest = zeroed();
+    for (index, ch_info) in ch_infos.iter().enumerate() {
+        request.offsets[index] = ch_info.line_offset;
+    }
+    request.num_lines = ch_infos.len() as u32;
+    request.consumer[..CONSUMER.len()].copy_from_slice(CONSUMER);
+    request.config = line_config(direction.clone(), pull, None, None);
+    if direction == Direction::OUT && drive == Drive::OPEN_DRAIN {
+        request.config.flags |= GPIO_V2_LINE_FLAG_OPEN_DRAIN;
+    }
+    if initials.iter().any(Option::is_some) {
+        set_config_output_values(&mut request.config, initials);
+    }
+
+    if let Err(e) = ioctl(&chip, GPIO_V2_GET_LINE_IOCTL, &mut request) {
+        if e.kind() == io::ErrorKind::ResourceBusy {
+            for ch_info in ch_infos {
+                if let Some(consumer) = line_consumer(ch_info).ok().flatten() {
+                    return Err(Error::new(GpioError::CHANNEL_BUSY(ch_info.channel.clone())).context(format!(
+                        "Channel {} is in use by \"{}\" (e.g. a device-tree GPIO hog or another process) and cannot be requested",
+                        ch_info.channel, consumer
+                    )));
+                }
+            }
+        }
+        let channels: Vec<String> = ch_infos.iter().map(|ch_info| ch_info.channel.to_string()).collect();
+        return Err(Error::new(e).context(format!("Failed to request channel {}", channels.join(", "))));
+    }
+
+    Ok(LineRequest {
+        // SAFETY: the kernel returned a new file descriptor that nothing else owns
+        file: unsafe { File::from_raw_fd(request.fd) },
+        ch_infos: ch_infos.to_vec(),
+    })
+}
+
+// Returns the debounce period a line is configured with, in microseconds.
+fn line_debounce_period_us(info: &GpioV2LineInfo) -> Option<u64> {
+    info.attrs[..(info.num_attrs as usize).min(GPIO_V2_LINE_NUM_ATTRS_MAX)]
+        .iter()
+        .find(|attr| attr.id == GPIO_V2_LINE_ATTR_ID_DEBOUNCE)
+        .map(|attr| attr.value)
+}
+
+// The configuration of one line of a request.
+struct LineSettings {
+    flags: u64,
+    value: Option<Level>,
+    debounce_period_us: Option<u64>,
+}
+
+// Builds a line config that configures every line of a request on its own. The
+// flags of the first line are the default, lines with other flags get an attribute.
+fn per_line_config(lines: &[LineSettings]) -> Result<GpioV2LineConfig, Error> {
+    let mut config: GpioV2LineConfig = zeroed();
+    config.flags = lines[0].flags;
+
+    let mut attrs: Vec<GpioV2LineConfigAttribute> = Vec::new();
+    let mut add_attr = |id: u32, value: u64, index: usize| {
+        match attrs.iter_mut().find(|attr| attr.attr.id == id && attr.attr.value == value) {
+            Some(attr) => attr.mask |= 1 << index,
+            None => attrs.push(GpioV2LineConfigAttribute {
+                attr: GpioV2LineAttribute { id, padding: 0, value },
+                mask: 1 << index,
+            }),
+        }
+    };
+    for (index, line) in lines.iter().enumerate() {
+        if line.flags != config.flags {
+            add_attr(GPIO_V2_LINE_ATTR_ID_FLAGS, line.flags, index);
+        }
+        if let Some(debounce_period_us) = line.debounce_period_us {
+            add_attr(GPIO_V2_LINE_ATTR_ID_DEBOUNCE, debounce_period_us, index);
+        }
+    }
+
+    let values: Vec<Option<Level>> = lines
+        .iter()
+        .map(|line| line.value.clone().filter(|_| line.flags & GPIO_V2_LINE_FLAG_OUTPUT != 0))
+        .collect();
+    let has_values = values.iter().any(Option::is_some);
+    if attrs.len() + has_values as usize > GPIO_V2_LINE_NUM_ATTRS_MAX {
+        return Err(Error::msg("The lines of the request differ in too many ways to configure them"));
+    }
+
+    config.attrs[..attrs.len()].copy_from_slice(&attrs);
+    config.num_attrs = attrs.len() as u32;
+    if has_values {
+        set_config_output_values(&mut config, &values);
+    }
+
+    Ok(config)
+}
+
+// Reconfigures the line of a channel in a request. A line config covers all
+// lines of a request, so the configuration and output values of the other
+// lines are read back and repeated, which leaves them untouched.
+fn reconfigure_line(
+    request: &LineRequest,
+    ch_info: &ChannelInfo,
+    update: impl FnOnce(&mut LineSettings),
+) -> Result<(), Error> {
+    let Some(index) = request.index_of(ch_info) else {
+        return Err(Error::msg(format!(
+            "Channel {} is not part of the line request",
+            ch_info.channel
+        )));
+    };
+
+    let bits = get_line_values(request, u64::MAX >> (64 - request.ch_infos.len()))?;
+    let mut lines = Vec::new();
+    for (other_index, other) in request.ch_infos.iter().enumerate() {
+        let info = line_info(other)?;
+        let flags = info.flags & !GPIO_V2_LINE_FLAG_USED;
+        let value = match bits & (1 << other_index) {
+            0 => Level::LOW,
+            _ => Level::HIGH,
+        };
+        lines.push(LineSettings {
+            flags,
+            value: (flags & GPIO_V2_LINE_FLAG_OUTPUT != 0).then_some(value),
+            debounce_period_us: line_debounce_period_us(&info),
+        });
+    }
+    update(&mut lines[index as usize]);
+
+    let mut config = per_line_config(&lines)?;
+    ioctl(request, GPIO_V2_LINE_SET_CONFIG_IOCTL, &mut config)?;
+
+    Ok(())
+}
+
+/// Configures the line of a channel in a request as if it had just been requested,
+/// leaving the other lines of the request untouched.
+pub(crate) fn set_request_line(
+    request: &LineRequest,
+    ch_info: &ChannelInfo,
+    direction: Direction,
+    pull: Pull,
+    initial: Option<Level>,
+    drive: Drive,
+) -> Result<(), Error> {
+    reconfigure_line(request, ch_info, |line| {
+        line.flags = line_config(direction.clone(), pull, None, None).flags;
+        if direction == Direction::OUT && drive == Drive::OPEN_DRAIN {
+            line.flags |= GPIO_V2_LINE_FLAG_OPEN_DRAIN;
+        }
+        line.value = initial;
+        line.debounce_period_us = None;
+    })
+}
+
+/// Stops driving the line of a channel in a request by making it an input
+/// without bias, leaving the other lines of the request untouched.
+pub(crate) fn release_request_line(request: &LineRequest, ch_info: &ChannelInfo) -> Result<(), Error> {
+    reconfigure_line(request, ch_info, |line| {
+        *line = LineSettings {
+            flags: GPIO_V2_LINE_FLAG_INPUT,
+            value: None,
+            debounce_period_us: None,
+        };
+    })
+}
+
+/// Enables or disables edge detection on a requested input line, keeping its bias and debounce period.
+pub(crate) fn set_line_edge(ch_info: &ChannelInfo, request: &LineRequest, edge: Option<Edge>) -> Result<(), Error> {
+    reconfigure_line(request, ch_info, |line| {
+        line.flags = line_config(Direction::IN, Pull::OFF, None, edge).flags | (line.flags & GPIO_V2_LINE_FLAG_BIAS);
+    })
+}
+
+/// Debounces a requested input line in the kernel, keeping its bias.
+///
+/// The kernel uses the debounce of the GPIO controller if it has one and
+/// debounces in software otherwise. Kernels older than 5.10 reject the request.
+pub(crate) fn set_line_debounce(ch_info: &ChannelInfo, request: &LineRequest, debounce: Duration) -> Result<(), Error> {
+    reconfigure_line(request, ch_info, |line| {
+        line.flags = line_config(Direction::IN, Pull::OFF, None, None).flags | (line.flags & GPIO_V2_LINE_FLAG_BIAS);
+        line.debounce_period_us = Some(debounce.as_micros() as u64);
+    })
+}
+
+/// Reads the value of the line at `index` of a request.
+pub(crate) fn get_line_value(line: &File, index: u32) -> Result<Level, Error> {
+    match get_line_values(line, 1 << index)? & (1 << index) {
+        0 => Ok(Level::LOW),
+        _ => Ok(Level::HIGH),
+    }
+}
+
+/// Reads the values of the lines of a request selected by `mask` with a single ioctl
+/// and returns them as a bitmap indexed like the mask.
+pub(crate) fn get_line_values(line: &File, mask: u64) -> Result<u64, Error> {
+    let mut values = GpioV2LineValues { bits: 0, mask };
+    ioctl(line, GPIO_V2_LINE_GET_VALUES_IOCTL, &mut values)?;
+
+    Ok(values.bits & mask)
+}
+
+/// Writes the value of the line at `index` of a request.
+pub(crate) fn set_line_value(line: &File, index: u32, value: Level) -> Result<(), Error> {
+    set_line_values(line, &[(index, value)])
+}
+
+/// Writes the values of several lines of a request with a single ioctl.
+pub(crate) fn set_line_values(line: &File, values: &[(u32, Level)]) -> Result<(), Error> {
+    let mut line_values = GpioV2LineValues { bits: 0, mask: 0 };
+    for (index, value) in values {
+        line_values.mask |= 1 << index;
+        if *value == Level::HIGH {
+            line_values.bits |= 1 << index;
+        }
+    }
+    ioctl(line, GPIO_V2_LINE_SET_VALUES_IOCTL, &mut line_values)?;
+
+    Ok(())
+}
+
+/// Reads the next edge event of a line with edge detection enabled and returns the level after
+/// the edge, together with the `CLOCK_MONOTONIC` time the kernel detected it at.
+pub(crate) fn read_line_event(mut line: &File) -> io::Result<(Level, Duration)> {
+    let mut buf = [0u8; mem::size_of::<GpioV2LineEvent>()];
+    line.read_exact(&mut buf)?;
+    // SAFETY: the buffer holds exactly one event as written by the kernel
+    let event: GpioV2LineEvent = unsafe { std::ptr::read_unaligned(buf.as_ptr() as *const GpioV2LineEvent) };
+
+    let level = match event.id {
+        GPIO_V2_LINE_EVENT_RISING_EDGE => Level::HIGH,
+        _ => Level::LOW,
+    };
+    Ok((level, Duration::from_nanos(event.timestamp_ns)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uapi_structs_match_kernel_layout() {
+        assert_eq!(mem::size_of::<GpioChipInfo>(), 68);
+        assert_eq!(mem::size_of::<GpioV2LineConfig>(), 272);
+        assert_eq!(mem::size_of::<GpioV2LineRequest>(), 592);
+        assert_eq!(mem::size_of::<GpioV2LineValues>(), 16);
+        assert_eq!(mem::size_of::<GpioV2LineInfo>(), 256);
+        assert_eq!(mem::size_of::<GpioV2LineEvent>(), 48);
+        assert_eq!(GPIO_V2_GET_LINE_IOCTL, 0xC250_B407);
+    }
+
+    #[test]
+    fn pull_sets_bias_flags() {
+        let config = line_config(Direction::IN, Pull::UP, None, Some(Edge::RISING));
+        assert_eq!(
+            config.flags,
+            GPIO_V2_LINE_FLAG_INPUT | GPIO_V2_LINE_FLAG_BIAS_PULL_UP | GPIO_V2_LINE_FLAG_EDGE_RISING
+        );
+
+        let config = line_config(Direction::IN, Pull::DOWN, None, None);
+        assert_eq!(config.flags, GPIO_V2_LINE_FLAG_INPUT | GPIO_V2_LINE_FLAG_BIAS_PULL_DOWN);
+
+        let config = line_config(Direction::IN, Pull::OFF, None, None);
+        assert_eq!(config.flags & GPIO_V2_LINE_FLAG_BIAS, 0);
+    }
+
+    #[test]
+    fn output_values_are_indexed_by_line() {
+        let mut config = line_config(Direction::OUT, Pull::OFF, None, None);
+        set_config_output_values(&mut config, &[Some(Level::HIGH), None, Some(Level::LOW)]);
+        assert_eq!(config.num_attrs, 1);
+        assert_eq!(config.attrs[0].attr.value, 0b001);
+        assert_eq!(config.attrs[0].mask, 0b101);
+    }
+
+    #[test]
+    fn lines_of_a_request_are_configured_on_their_own() {
+        let output = LineSettings {
+            flags: GPIO_V2_LINE_FLAG_OUTPUT,
+            value: Some(Level::HIGH),
+            debounce_period_us: None,
+        };
+        let input = LineSettings {
+            flags: GPIO_V2_LINE_FLAG_INPUT,
+            value: None,
+            debounce_period_us: Some(5000),
+        };
+        let config = per_line_config(&[output, input]).unwrap();
+
+        assert_eq!(config.flags, GPIO_V2_LINE_FLAG_OUTPUT);
+        assert_eq!(config.num_attrs, 3);
+        assert_eq!(config.attrs[0].attr.id, GPIO_V2_LINE_ATTR_ID_FLAGS);
+        assert_eq!(config.attrs[0].attr.value, GPIO_V2_LINE_FLAG_INPUT);
+        assert_eq!(config.attrs[0].mask, 0b10);
+        assert_eq!(config.attrs[1].attr.id, GPIO_V2_LINE_ATTR_ID_DEBOUNCE);
+        assert_eq!(config.attrs[1].attr.value, 5000);
+        assert_eq!(config.attrs[1].mask, 0b10);
+        assert_eq!(config.attrs[2].attr.id, GPIO_V2_LINE_ATTR_ID_OUTPUT_VALUES);
+        assert_eq!(config.attrs[2].attr.value, 0b01);
+        assert_eq!(config.attrs[2].mask, 0b01);
+    }
+}
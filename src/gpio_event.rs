@@ -0,0 +1,529 @@
+use anyhow::Error;
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    os::unix::{io::AsRawFd, net::UnixStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tokio")]
+use futures_core::Stream;
+#[cfg(feature = "tokio")]
+use std::{
+    future::Future,
+    marker::PhantomData,
+    os::unix::io::RawFd,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+#[cfg(feature = "tokio")]
+use tokio::io::{unix::AsyncFd, Interest};
+
+#[cfg(feature = "tokio")]
+use crate::gpio::GPIO;
+
+use crate::gpio::{open_value_file, read_value_file, Level};
+use crate::gpio_cdev::{read_line_event, set_line_edge, LineRequest};
+use crate::gpio_paths::sysfs_gpio_root;
+use crate::gpio_pin_data::{Channel, ChannelInfo};
+
+/// Specifies the signal edge to detect on an input.
+///
+/// * `RISING` - Transition from LOW to HIGH
+/// * `FALLING` - Transition from HIGH to LOW
+/// * `BOTH` - Any transition
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use jetson_gpio::{GPIO, Direction, Edge, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+/// gpio.setup(vec![18], Direction::IN, None, None).unwrap();
+///
+/// gpio.wait_for_edge(18, Edge::RISING, Some(Duration::from_secs(1))).unwrap();
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Edge {
+    RISING = 31,
+    FALLING = 32,
+    BOTH = 33,
+}
+
+impl Edge {
+    fn to_sysfs(self) -> &'static str {
+        match self {
+            Edge::RISING => "rising",
+            Edge::FALLING => "falling",
+            Edge::BOTH => "both",
+        }
+    }
+
+    // The transition that was detected while waiting for this edge and left the
+    // input at the given level.
+    fn detected(self, level: Level) -> Edge {
+        match (self, level) {
+            (Edge::BOTH, Level::HIGH) => Edge::RISING,
+            (Edge::BOTH, Level::LOW) => Edge::FALLING,
+            (edge, _) => edge,
+        }
+    }
+}
+
+/// An edge detected on a channel, yielded by `GPIO::edge_stream`.
+///
+/// * `edge` - The detected transition, `RISING` or `FALLING`
+/// * `timestamp` - The `CLOCK_MONOTONIC` time of the edge. The character device
+///   backend reports the time the kernel detected the edge at, the sysfs backend
+///   the time the edge was read, which is later by the latency of the wake-up.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EdgeEvent {
+    pub edge: Edge,
+    pub timestamp: Duration,
+}
+
+// The current `CLOCK_MONOTONIC` time, the clock of the character device events.
+fn monotonic_now() -> Duration {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: `ts` is a valid timespec for the duration of the call
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// A function called with the channel and its new level when an edge is detected.
+pub type EventCallback = Box<dyn FnMut(Channel, Level) + Send>;
+
+fn write_edge(ch_info: &ChannelInfo, edge: &str) -> Result<(), Error> {
+    let edge_path = format!("{}/{}/edge", sysfs_gpio_root(), ch_info.global_gpio_name);
+    let mut f_edge = fs::OpenOptions::new().write(true).open(edge_path)?;
+    f_edge.write_all(edge.as_bytes())?;
+    Ok(())
+}
+
+// The file that reports edges of a channel: the sysfs value file, or the
+// requested line of the character device backend.
+enum EdgeSource {
+    Sysfs(File),
+    Line(File),
+}
+
+impl EdgeSource {
+    // Enables edge detection on the channel.
+    fn arm(ch_info: &ChannelInfo, edge: Edge, line: Option<&Arc<LineRequest>>) -> Result<EdgeSource, Error> {
+        match line {
+            Some(line) => {
+                set_line_edge(ch_info, line, Some(edge))?;
+                Ok(EdgeSource::Line(line.try_clone()?))
+            }
+            None => {
+                write_edge(ch_info, edge.to_sysfs())?;
+                let f_value = open_value_file(ch_info)?;
+                // sysfs reports the current value as an event until it has been read once
+                read_value_file(&f_value)?;
+                Ok(EdgeSource::Sysfs(f_value))
+            }
+        }
+    }
+
+    fn file(&self) -> &File {
+        match self {
+            EdgeSource::Sysfs(file) | EdgeSource::Line(file) => file,
+        }
+    }
+
+    fn poll_events(&self) -> i16 {
+        match self {
+            EdgeSource::Sysfs(_) => libc::POLLPRI | libc::POLLERR,
+            EdgeSource::Line(_) => libc::POLLIN,
+        }
+    }
+
+    // Consumes a reported edge and returns the level after it.
+    fn read_level(&self) -> io::Result<Level> {
+        self.read_event().map(|(level, _)| level)
+    }
+
+    // Consumes a reported edge and returns the level after it and the time of the edge.
+    fn read_event(&self) -> io::Result<(Level, Duration)> {
+        match self {
+            EdgeSource::Sysfs(f_value) => {
+                let timestamp = monotonic_now();
+                match read_value_file(f_value)?.as_str() {
+                    "0" => Ok((Level::LOW, timestamp)),
+                    _ => Ok((Level::HIGH, timestamp)),
+                }
+            }
+            EdgeSource::Line(line) => read_line_event(line),
+        }
+    }
+}
+
+// Disables edge detection on the channel.
+fn disarm(ch_info: &ChannelInfo, line: Option<&Arc<LineRequest>>) -> Result<(), Error> {
+    match line {
+        Some(line) => set_line_edge(ch_info, line, None),
+        None => write_edge(ch_info, "none"),
+    }
+}
+
+// Waits until the source reports an edge or `wake` becomes readable.
+// Returns `Ok(false)` if the timeout expired first.
+fn poll_edge(source: &EdgeSource, wake: Option<&UnixStream>, timeout: Option<Duration>) -> io::Result<bool> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let mut fds = vec![libc::pollfd {
+        fd: source.file().as_raw_fd(),
+        events: source.poll_events(),
+        revents: 0,
+    }];
+    if let Some(wake) = wake {
+        fds.push(libc::pollfd {
+            fd: wake.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        });
+    }
+
+    loop {
+        let timeout_ms = match deadline {
+            Some(deadline) => deadline
+                .saturating_duration_since(Instant::now())
+                .as_millis()
+                .min(i32::MAX as u128) as i32,
+            None => -1,
+        };
+
+        // SAFETY: `fds` is a valid array of pollfd structs for the duration of the call
+        let ret = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout_ms) };
+        if ret < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+
+        if ret == 0 || fds.get(1).is_some_and(|wake| wake.revents != 0) {
+            return Ok(false);
+        }
+        return Ok(fds[0].revents != 0);
+    }
+}
+
+/// Blocks until the edge is detected on the channel or the timeout expires.
+///
+/// Returns the detected transition, `RISING` or `FALLING`, or `None` if the timeout expired.
+/// Edge detection is disabled again afterwards. `line` is the requested line of the
+/// character device backend, `None` for sysfs.
+pub(crate) fn blocking_wait_for_edge(
+    ch_info: &ChannelInfo,
+    edge: Edge,
+    timeout: Option<Duration>,
+    line: Option<&Arc<LineRequest>>,
+) -> Result<Option<Edge>, Error> {
+    let source = EdgeSource::arm(ch_info, edge, line)?;
+
+    let result = poll_edge(&source, None, timeout).and_then(|detected| match detected {
+        true => source.read_level().map(|level| Some(edge.detected(level))),
+        false => Ok(None),
+    });
+
+    disarm(ch_info, line)?;
+    Ok(result?)
+}
+
+/// Blocks until `reached` returns `true` or the timeout expires, and returns whether it did.
+///
+/// `reached` is called once edge detection is enabled and again after every
+/// detected edge, so an edge between the first check and the wait is not
+/// missed. `edge` must be the edge that leads to the awaited level. Edge
+/// detection is disabled again afterwards. `line` is the requested line of the
+/// character device backend, `None` for sysfs.
+pub(crate) fn blocking_wait_for_level(
+    ch_info: &ChannelInfo,
+    edge: Edge,
+    timeout: Option<Duration>,
+    line: Option<&Arc<LineRequest>>,
+    mut reached: impl FnMut() -> Result<bool, Error>,
+) -> Result<bool, Error> {
+    let source = EdgeSource::arm(ch_info, edge, line)?;
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    let result = loop {
+        match reached() {
+            Ok(true) => break Ok(true),
+            Ok(false) => {}
+            Err(e) => break Err(e),
+        }
+
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match poll_edge(&source, None, remaining) {
+            Ok(true) => {}
+            Ok(false) => break Ok(false),
+            Err(e) => break Err(e.into()),
+        }
+        if let Err(e) = source.read_level() {
+            break Err(e.into());
+        }
+    };
+
+    disarm(ch_info, line)?;
+    result
+}
+
+/// Counts the edges detected on the channel until `duration` has passed.
+///
+/// Edge detection is disabled again afterwards. `line` is the requested line of
+/// the character device backend, `None` for sysfs.
+pub(crate) fn blocking_count_edges(
+    ch_info: &ChannelInfo,
+    edge: Edge,
+    duration: Duration,
+    line: Option<&Arc<LineRequest>>,
+) -> Result<u64, Error> {
+    let source = EdgeSource::arm(ch_info, edge, line)?;
+    let deadline = Instant::now() + duration;
+
+    let mut count = 0;
+    let result = loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match poll_edge(&source, None, Some(remaining)) {
+            Ok(true) => match source.read_level() {
+                Ok(_) => count += 1,
+                Err(e) => break Err(e),
+            },
+            Ok(false) => break Ok(count),
+            Err(e) => break Err(e),
+        }
+    };
+
+    disarm(ch_info, line)?;
+    Ok(result?)
+}
+
+// Disables edge detection on the channel when dropped, also if the future or
+// stream that armed it is dropped early.
+#[cfg(feature = "tokio")]
+struct Disarm {
+    ch_info: ChannelInfo,
+    line: Option<Arc<LineRequest>>,
+}
+
+#[cfg(feature = "tokio")]
+impl Disarm {
+    fn new(ch_info: &ChannelInfo, line: Option<&Arc<LineRequest>>) -> Disarm {
+        Disarm {
+            ch_info: ch_info.clone(),
+            line: line.cloned(),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for Disarm {
+    fn drop(&mut self) {
+        let _ = disarm(&self.ch_info, self.line.as_ref());
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsRawFd for EdgeSource {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file().as_raw_fd()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl EdgeSource {
+    // Arms the channel and registers the source with the tokio runtime.
+    fn arm_async(
+        ch_info: &ChannelInfo,
+        edge: Edge,
+        line: Option<&Arc<LineRequest>>,
+    ) -> Result<AsyncFd<EdgeSource>, Error> {
+        let source = EdgeSource::arm(ch_info, edge, line)?;
+        // sysfs signals edges as priority data, the line requests as readable data
+        let interest = match source {
+            EdgeSource::Sysfs(_) => Interest::PRIORITY,
+            EdgeSource::Line(_) => Interest::READABLE,
+        };
+        Ok(AsyncFd::with_interest(source, interest)?)
+    }
+
+    // Waits for the next reported edge and returns it.
+    async fn next_event(source: &AsyncFd<EdgeSource>, edge: Edge) -> io::Result<EdgeEvent> {
+        let interest = match source.get_ref() {
+            EdgeSource::Sysfs(_) => Interest::PRIORITY,
+            EdgeSource::Line(_) => Interest::READABLE,
+        };
+
+        loop {
+            let mut guard = source.ready(interest).await?;
+            if let Ok(event) = guard.try_io(|source| source.get_ref().read_event()) {
+                let (level, timestamp) = event?;
+                return Ok(EdgeEvent {
+                    edge: edge.detected(level),
+                    timestamp,
+                });
+            }
+        }
+    }
+}
+
+/// Waits on the tokio runtime until the edge is detected on the channel.
+///
+/// Returns the detected transition, like `blocking_wait_for_edge`. Edge detection
+/// is disabled again afterwards, or when the returned future is dropped.
+#[cfg(feature = "tokio")]
+pub(crate) async fn async_wait_for_edge(
+    ch_info: &ChannelInfo,
+    edge: Edge,
+    line: Option<&Arc<LineRequest>>,
+) -> Result<Edge, Error> {
+    let _disarm = Disarm::new(ch_info, line);
+    let source = EdgeSource::arm_async(ch_info, edge, line)?;
+
+    Ok(EdgeSource::next_event(&source, edge).await?.edge)
+}
+
+#[cfg(feature = "tokio")]
+type NextEdgeEvent = Pin<Box<dyn Future<Output = io::Result<EdgeEvent>> + Send>>;
+
+/// A stream of the edges detected on a channel, created with `GPIO::edge_stream`.
+///
+/// The stream ends if reading an edge fails. Dropping it disables edge detection on
+/// the channel. It borrows the `GPIO` object, so the channel cannot be cleaned up
+/// while the stream exists.
+#[cfg(feature = "tokio")]
+pub struct EdgeStream<'a> {
+    source: Arc<AsyncFd<EdgeSource>>,
+    edge: Edge,
+    next: Option<NextEdgeEvent>,
+    ended: bool,
+    _disarm: Disarm,
+    _gpio: PhantomData<&'a GPIO>,
+}
+
+#[cfg(feature = "tokio")]
+impl EdgeStream<'_> {
+    pub(crate) fn start(ch_info: &ChannelInfo, edge: Edge, line: Option<&Arc<LineRequest>>) -> Result<Self, Error> {
+        let disarm = Disarm::new(ch_info, line);
+        let source = EdgeSource::arm_async(ch_info, edge, line)?;
+
+        Ok(EdgeStream {
+            source: Arc::new(source),
+            edge,
+            next: None,
+            ended: false,
+            _disarm: disarm,
+            _gpio: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Stream for EdgeStream<'_> {
+    type Item = EdgeEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<EdgeEvent>> {
+        let this = &mut *self;
+        if this.ended {
+            return Poll::Ready(None);
+        }
+
+        let source = this.source.clone();
+        let edge = this.edge;
+        let next = this
+            .next
+            .get_or_insert_with(|| Box::pin(async move { EdgeSource::next_event(&source, edge).await }));
+
+        let event = ready!(next.as_mut().poll(cx));
+        this.next = None;
+        this.ended = event.is_err();
+        Poll::Ready(event.ok())
+    }
+}
+
+/// A background thread that watches a channel for edges.
+///
+/// Every detected edge sets a flag that is read with `take_event` and calls the
+/// registered callbacks with the level read right after the edge. Dropping the
+/// detector stops the thread and disables edge detection on the channel.
+pub(crate) struct EventDetector {
+    ch_info: ChannelInfo,
+    line: Option<Arc<LineRequest>>,
+    edge: Edge,
+    event_occurred: Arc<AtomicBool>,
+    callbacks: Arc<Mutex<Vec<EventCallback>>>,
+    wake: UnixStream,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl EventDetector {
+    pub(crate) fn start(
+        ch_info: ChannelInfo,
+        edge: Edge,
+        line: Option<Arc<LineRequest>>,
+    ) -> Result<EventDetector, Error> {
+        let source = EdgeSource::arm(&ch_info, edge, line.as_ref())?;
+
+        let (wake, wake_thread) = UnixStream::pair()?;
+        let event_occurred = Arc::new(AtomicBool::new(false));
+        let callbacks: Arc<Mutex<Vec<EventCallback>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let channel = ch_info.channel.clone();
+        let thread_event_occurred = event_occurred.clone();
+        let thread_callbacks = callbacks.clone();
+        // runs until woken up to stop, or until polling fails
+        let thread = thread::spawn(move || {
+            while let Ok(true) = poll_edge(&source, Some(&wake_thread), None) {
+                let Ok(level) = source.read_level() else {
+                    break;
+                };
+                thread_event_occurred.store(true, Ordering::SeqCst);
+                for callback in thread_callbacks.lock().unwrap().iter_mut() {
+                    callback(channel.clone(), level.clone());
+                }
+            }
+        });
+
+        Ok(EventDetector {
+            ch_info,
+            line,
+            edge,
+            event_occurred,
+            callbacks,
+            wake,
+            thread: Some(thread),
+        })
+    }
+
+    pub(crate) fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    pub(crate) fn add_callback(&self, callback: EventCallback) {
+        self.callbacks.lock().unwrap().push(callback);
+    }
+
+    pub(crate) fn take_event(&self) -> bool {
+        self.event_occurred.swap(false, Ordering::SeqCst)
+    }
+}
+
+impl Drop for EventDetector {
+    fn drop(&mut self) {
+        let _ = self.wake.write_all(&[0]);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = disarm(&self.ch_info, self.line.as_ref());
+    }
+}
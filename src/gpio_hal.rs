@@ -0,0 +1,70 @@
+// embedded-hal digital traits for the pin handles, so they can be passed to
+// drivers written against embedded-hal.
+
+use embedded_hal::digital::{self, ErrorKind, ErrorType};
+use std::fmt;
+
+use crate::gpio::Level;
+use crate::gpio_pin::{InputPin, OutputPin};
+
+/// The error returned by the embedded-hal traits of `OutputPin` and `InputPin`.
+///
+/// Wraps the error of the underlying operation.
+#[derive(Debug)]
+pub struct PinError(pub anyhow::Error);
+
+impl fmt::Display for PinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for PinError {}
+
+impl digital::Error for PinError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl ErrorType for OutputPin {
+    type Error = PinError;
+}
+
+impl digital::OutputPin for OutputPin {
+    fn set_low(&mut self) -> Result<(), PinError> {
+        OutputPin::set_low(self).map_err(PinError)
+    }
+
+    fn set_high(&mut self) -> Result<(), PinError> {
+        OutputPin::set_high(self).map_err(PinError)
+    }
+}
+
+impl digital::StatefulOutputPin for OutputPin {
+    fn is_set_high(&mut self) -> Result<bool, PinError> {
+        Ok(self.read().map_err(PinError)? == Level::HIGH)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, PinError> {
+        Ok(self.read().map_err(PinError)? == Level::LOW)
+    }
+
+    fn toggle(&mut self) -> Result<(), PinError> {
+        OutputPin::toggle(self).map_err(PinError)
+    }
+}
+
+impl ErrorType for InputPin {
+    type Error = PinError;
+}
+
+impl digital::InputPin for InputPin {
+    fn is_high(&mut self) -> Result<bool, PinError> {
+        InputPin::is_high(self).map_err(PinError)
+    }
+
+    fn is_low(&mut self) -> Result<bool, PinError> {
+        InputPin::is_low(self).map_err(PinError)
+    }
+}
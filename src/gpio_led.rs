@@ -0,0 +1,114 @@
+use anyhow::Error;
+
+use crate::gpio::Level;
+use crate::gpio_pin::OutputPin;
+use crate::gpio_pin_data::Channel;
+use crate::gpio_pwm::PWM;
+
+// How an LED is driven: an output pin that can only switch it, or a hardware PWM
+// whose duty cycle sets the brightness.
+enum LedOutput {
+    Pin(OutputPin),
+    Pwm(PWM),
+}
+
+/// An LED on a header pin, created with `GPIO::led` or `GPIO::led_pwm`.
+///
+/// Every LED can be switched on and off. LEDs on a PWM channel created with
+/// `led_pwm` can also be dimmed with `set_brightness`, which returns an error
+/// for LEDs driven by a plain output.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// let mut led = gpio.led_pwm(33, 1000.0).unwrap();
+/// led.on().unwrap();
+/// led.set_brightness(0.25).unwrap();
+/// led.off().unwrap();
+/// ```
+pub struct Led {
+    channel: Channel,
+    output: LedOutput,
+    running: bool,
+}
+
+impl Led {
+    pub(crate) fn with_pin(pin: OutputPin) -> Led {
+        Led {
+            channel: pin.channel().clone(),
+            output: LedOutput::Pin(pin),
+            running: false,
+        }
+    }
+
+    pub(crate) fn with_pwm(channel: Channel, pwm: PWM) -> Led {
+        Led {
+            channel,
+            output: LedOutput::Pwm(pwm),
+            running: false,
+        }
+    }
+
+    /// Returns the channel of the LED.
+    pub fn channel(&self) -> &Channel {
+        &self.channel
+    }
+
+    /// Returns `true` if the brightness of the LED can be set.
+    pub fn is_dimmable(&self) -> bool {
+        matches!(self.output, LedOutput::Pwm(_))
+    }
+
+    /// Switches the LED on at full brightness.
+    pub fn on(&mut self) -> Result<(), Error> {
+        match &self.output {
+            LedOutput::Pin(pin) => pin.set(Level::HIGH),
+            LedOutput::Pwm(_) => self.set_brightness(1.0),
+        }
+    }
+
+    /// Switches the LED off.
+    pub fn off(&mut self) -> Result<(), Error> {
+        match &self.output {
+            LedOutput::Pin(pin) => pin.set(Level::LOW),
+            LedOutput::Pwm(_) => self.set_brightness(0.0),
+        }
+    }
+
+    /// Sets the brightness of the LED.
+    ///
+    /// Returns an error if the LED is not on a PWM channel, see `is_dimmable`.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - The brightness, between 0.0 (off) and 1.0 (full brightness)
+    pub fn set_brightness(&mut self, brightness: f64) -> Result<(), Error> {
+        if !(0.0..=1.0).contains(&brightness) {
+            return Err(Error::msg("The brightness must be between 0.0 and 1.0"));
+        }
+
+        let pwm = match &mut self.output {
+            LedOutput::Pwm(pwm) => pwm,
+            LedOutput::Pin(_) => {
+                return Err(Error::msg(format!(
+                    "The brightness of the LED on channel {} cannot be set, use led_pwm on a PWM channel",
+                    self.channel
+                )))
+            }
+        };
+
+        match self.running {
+            true => pwm.change_duty_cycle(brightness * 100.0),
+            false => {
+                pwm.start(brightness * 100.0)?;
+                self.running = true;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::Error;
+use std::{collections::HashMap, sync::Mutex};
+
+use crate::gpio::{Direction, Level, Pull};
+use crate::gpio_pin_data::ChannelInfo;
+
+struct MockPin {
+    direction: Direction,
+    value: Level,
+}
+
+/// The in-memory pins of the mock backend.
+///
+/// Pins are keyed by their global GPIO number, so the channels of a pin in
+/// the different numbering modes share its state. A pin exists from the time
+/// it is set up until it is cleaned up, like an exported sysfs GPIO.
+#[derive(Default)]
+pub(crate) struct MockPins {
+    pins: Mutex<HashMap<u32, MockPin>>,
+}
+
+impl MockPins {
+    pub(crate) fn export(&self, ch_info: &ChannelInfo, direction: Direction, pull: Pull, initial: Option<Level>) {
+        let value = match (initial, pull) {
+            (Some(initial), _) => initial,
+            (None, Pull::UP) => Level::HIGH,
+            (None, _) => Level::LOW,
+        };
+
+        self.pins
+            .lock()
+            .unwrap()
+            .insert(ch_info.global_gpio, MockPin { direction, value });
+    }
+
+    pub(crate) fn unexport(&self, ch_info: &ChannelInfo) {
+        self.pins.lock().unwrap().remove(&ch_info.global_gpio);
+    }
+
+    pub(crate) fn get(&self, ch_info: &ChannelInfo) -> Result<Level, Error> {
+        match self.pins.lock().unwrap().get(&ch_info.global_gpio) {
+            Some(pin) => Ok(pin.value.clone()),
+            None => Err(Error::msg(format!("Channel {} is not exported", ch_info.channel))),
+        }
+    }
+
+    /// Sets the level of a pin, either written by the program to an output or
+    /// driven by the simulated outside world on an input.
+    pub(crate) fn set(&self, ch_info: &ChannelInfo, direction: Direction, value: Level) -> Result<(), Error> {
+        match self.pins.lock().unwrap().get_mut(&ch_info.global_gpio) {
+            Some(pin) if pin.direction == direction => {
+                pin.value = value;
+                Ok(())
+            }
+            Some(pin) => Err(Error::msg(format!(
+                "Channel {} is set up as {}, not {}",
+                ch_info.channel, pin.direction, direction
+            ))),
+            None => Err(Error::msg(format!("Channel {} is not exported", ch_info.channel))),
+        }
+    }
+}
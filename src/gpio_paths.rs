@@ -0,0 +1,70 @@
+use std::{
+    env,
+    sync::{PoisonError, RwLock},
+};
+
+/// The locations of the kernel interfaces used to find and control the GPIOs.
+///
+/// By default, each location is read from an environment variable, falling back
+/// to the standard location of a Jetson board. Set them with `GPIO::new_with_paths`
+/// to run against a chroot or a bind-mounted tree, e.g. in containers or tests.
+/// The locations describe the system, so they apply to every `GPIO` object of
+/// the process.
+///
+/// * `sysfs` - The sysfs mount point, `JETSON_GPIO_SYSFS` or `/sys`
+/// * `device_tree` - The device tree, `JETSON_GPIO_DEVICE_TREE` or `/proc/device-tree`
+/// * `dev` - The directory of the GPIO character devices, `JETSON_GPIO_DEV` or `/dev`
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Backend, GpioPaths};
+///
+/// let paths = GpioPaths {
+///     sysfs: String::from("/host/sys"),
+///     ..GpioPaths::default()
+/// };
+/// let gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpioPaths {
+    pub sysfs: String,
+    pub device_tree: String,
+    pub dev: String,
+}
+
+impl Default for GpioPaths {
+    fn default() -> Self {
+        let var = |name: &str, default: &str| env::var(name).unwrap_or_else(|_| String::from(default));
+
+        GpioPaths {
+            sysfs: var("JETSON_GPIO_SYSFS", "/sys"),
+            device_tree: var("JETSON_GPIO_DEVICE_TREE", "/proc/device-tree"),
+            dev: var("JETSON_GPIO_DEV", "/dev"),
+        }
+    }
+}
+
+static PATHS: RwLock<Option<GpioPaths>> = RwLock::new(None);
+
+// Returns the locations set with `set_paths`, or the default ones.
+pub(crate) fn paths() -> GpioPaths {
+    match PATHS.read().unwrap_or_else(PoisonError::into_inner).as_ref() {
+        Some(paths) => paths.clone(),
+        None => GpioPaths::default(),
+    }
+}
+
+pub(crate) fn set_paths(paths: GpioPaths) {
+    *PATHS.write().unwrap_or_else(PoisonError::into_inner) = Some(paths);
+}
+
+// The sysfs GPIO class, `/sys/class/gpio` by default.
+pub(crate) fn sysfs_gpio_root() -> String {
+    format!("{}/class/gpio", paths().sysfs)
+}
+
+// A path inside the device tree, e.g. `compatible`.
+pub(crate) fn device_tree_path(path: &str) -> String {
+    format!("{}/{}", paths().device_tree, path)
+}
@@ -0,0 +1,166 @@
+use anyhow::Error;
+use std::{fs::File, os::unix::fs::FileExt, sync::Arc};
+
+use crate::gpio::{Direction, Level};
+use crate::gpio_cdev::{get_line_value, set_line_value};
+use crate::gpio_mock::MockPins;
+use crate::gpio_pin_data::{Channel, ChannelInfo};
+
+// How a pin handle reaches its pin: the sysfs value file, the requested line of
+// the character device backend with its index in the request, or the in-memory
+// pins of the mock backend.
+pub(crate) enum PinIo {
+    Sysfs(File),
+    Line(File, u32),
+    Mock(Arc<MockPins>),
+}
+
+impl PinIo {
+    fn read(&self, ch_info: &ChannelInfo, active_low: bool) -> Result<Level, Error> {
+        let value = match self {
+            PinIo::Sysfs(f_value) => {
+                let mut value = [0u8; 1];
+                f_value.read_exact_at(&mut value, 0)?;
+                match value[0] {
+                    b'0' => Level::LOW,
+                    _ => Level::HIGH,
+                }
+            }
+            PinIo::Line(line, index) => get_line_value(line, *index)?,
+            PinIo::Mock(mock_pins) => mock_pins.get(ch_info)?,
+        };
+
+        match active_low {
+            true => Ok(value.inverted()),
+            false => Ok(value),
+        }
+    }
+
+    fn write(&self, ch_info: &ChannelInfo, value: Level, active_low: bool) -> Result<(), Error> {
+        let value = if active_low { value.inverted() } else { value };
+
+        match self {
+            PinIo::Sysfs(f_value) => {
+                f_value.write_all_at(value.sysfs_str().as_bytes(), 0)?;
+                Ok(())
+            }
+            PinIo::Line(line, index) => set_line_value(line, *index, value),
+            PinIo::Mock(mock_pins) => mock_pins.set(ch_info, Direction::OUT, value),
+        }
+    }
+}
+
+/// A handle to a single output channel, created with `GPIO::setup_output_pin`.
+///
+/// The handle keeps the channel information and an open file descriptor, so
+/// writing to the pin skips the channel lookup and validation done by
+/// `GPIO::output`. It stays usable independently of the `GPIO` object, but
+/// fails once the channel has been cleaned up. The handle keeps the active-low
+/// setting the channel had when the handle was created.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// let led = gpio.setup_output_pin(7, None).unwrap();
+/// led.set_high().unwrap();
+/// led.toggle().unwrap();
+/// ```
+pub struct OutputPin {
+    ch_info: ChannelInfo,
+    io: PinIo,
+    active_low: bool,
+}
+
+impl OutputPin {
+    pub(crate) fn new(ch_info: ChannelInfo, io: PinIo, active_low: bool) -> OutputPin {
+        OutputPin { ch_info, io, active_low }
+    }
+
+    /// Returns the channel of the pin.
+    pub fn channel(&self) -> &Channel {
+        &self.ch_info.channel
+    }
+
+    /// Drives the pin to the given level.
+    pub fn set(&self, value: Level) -> Result<(), Error> {
+        self.io.write(&self.ch_info, value, self.active_low)
+    }
+
+    /// Drives the pin HIGH.
+    pub fn set_high(&self) -> Result<(), Error> {
+        self.set(Level::HIGH)
+    }
+
+    /// Drives the pin LOW.
+    pub fn set_low(&self) -> Result<(), Error> {
+        self.set(Level::LOW)
+    }
+
+    /// Returns the level the pin is currently driven to.
+    pub fn read(&self) -> Result<Level, Error> {
+        self.io.read(&self.ch_info, self.active_low)
+    }
+
+    /// Drives the pin to the opposite of its current level.
+    pub fn toggle(&self) -> Result<(), Error> {
+        match self.read()? {
+            Level::HIGH => self.set_low(),
+            Level::LOW => self.set_high(),
+        }
+    }
+}
+
+/// A handle to a single input channel, created with `GPIO::setup_input_pin`.
+///
+/// Like `OutputPin`, the handle keeps the channel information and an open file
+/// descriptor, so reading the pin skips the checks done by `GPIO::input`.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Mode, Pull};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// let button = gpio.setup_input_pin(12, Pull::UP).unwrap();
+/// if button.is_low().unwrap() {
+///     println!("Button pressed");
+/// }
+/// ```
+pub struct InputPin {
+    ch_info: ChannelInfo,
+    io: PinIo,
+    active_low: bool,
+}
+
+impl InputPin {
+    pub(crate) fn new(ch_info: ChannelInfo, io: PinIo, active_low: bool) -> InputPin {
+        InputPin { ch_info, io, active_low }
+    }
+
+    /// Returns the channel of the pin.
+    pub fn channel(&self) -> &Channel {
+        &self.ch_info.channel
+    }
+
+    /// Returns the current level of the pin.
+    pub fn read(&self) -> Result<Level, Error> {
+        self.io.read(&self.ch_info, self.active_low)
+    }
+
+    /// Returns `true` if the pin is HIGH.
+    pub fn is_high(&self) -> Result<bool, Error> {
+        Ok(self.read()? == Level::HIGH)
+    }
+
+    /// Returns `true` if the pin is LOW.
+    pub fn is_low(&self) -> Result<bool, Error> {
+        Ok(self.read()? == Level::LOW)
+    }
+}
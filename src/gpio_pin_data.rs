@@ -4,10 +4,14 @@ use std::{
     collections::HashMap,
     env,
     fs::{self, File},
+    fmt,
     io::Read,
     path::Path,
 };
 
+use crate::gpio_cdev::{chip_lines, find_gpio_chip_dev};
+use crate::gpio_paths::{device_tree_path, paths};
+
 /// Specifies the pin numbering mode.
 ///
 /// The pin numbering mode is used to determine the mapping between the pin numbers
@@ -15,8 +19,8 @@ use std::{
 ///
 /// * `BOARD` - The pin numbers are the physical pin numbers on the Jetson board.
 /// * `BCM` - The pin numbers are the Broadcom SOC channel numbers.
-/// * `TEGRA_SOC` - The pin numbers are the Tegra SOC channel numbers.
-/// * `CVM` - The pin numbers are the CVM channel numbers.
+/// * `TEGRA_SOC` - The pins are identified by their Tegra SOC names, e.g. `"GP66"`.
+/// * `CVM` - The pins are identified by their CVM signal names, e.g. `"GPIO27"`.
 ///
 /// # Example
 ///
@@ -26,12 +30,20 @@ use std::{
 /// let mut gpio = GPIO::new();
 /// gpio.setmode(Mode::BOARD).unwrap();
 /// ```
-#[derive(Eq, Hash, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
 pub enum Mode {
     BOARD,
     BCM,
-    // TEGRA_SOC,
-    // CVM,
+    TEGRA_SOC,
+    CVM,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
 }
 
 impl Mode {
@@ -42,7 +54,7 @@ impl Mode {
     /// * `"BOARD"`
     /// * `"BCM"`
     /// * `"TEGRA_SOC"`
-    /// * `CVM`
+    /// * `"CVM"`
     ///
     /// # Example
     ///
@@ -56,8 +68,8 @@ impl Mode {
         match s {
             "BOARD" => Ok(Mode::BOARD),
             "BCM" => Ok(Mode::BCM),
-            // "TEGRA_SOC" => Ok(Mode::TEGRA_SOC),
-            // "CVM" => Ok(Mode::CVM),
+            "TEGRA_SOC" => Ok(Mode::TEGRA_SOC),
+            "CVM" => Ok(Mode::CVM),
             _ => Err(anyhow!("Invalid mode: {}", s)),
         }
     }
@@ -77,8 +89,8 @@ impl Mode {
         match self {
             Mode::BOARD => "BOARD",
             Mode::BCM => "BCM",
-            // Mode::TEGRA_SOC => "TEGRA_SOC",
-            // Mode::CVM => "CVM",
+            Mode::TEGRA_SOC => "TEGRA_SOC",
+            Mode::CVM => "CVM",
         }
     }
 
@@ -92,20 +104,79 @@ impl Mode {
     /// let mut gpio = GPIO::new();
     /// assert_eq!(Mode::BOARD.is_valid(), true);
     /// assert_eq!(Mode::BCM.is_valid(), true);
-    /// assert_eq!(Mode::TEGRA_SOC.is_valid(), false);
-    /// assert_eq!(Mode::CVM.is_valid(), false);
+    /// assert_eq!(Mode::TEGRA_SOC.is_valid(), true);
+    /// assert_eq!(Mode::CVM.is_valid(), true);
     /// ```
     pub fn is_valid(&self) -> bool {
         match self {
             Mode::BOARD => true,
             Mode::BCM => true,
-            // Mode::TEGRA_SOC => true,
-            // Mode::CVM => true,
-            _ => false,
+            Mode::TEGRA_SOC => true,
+            Mode::CVM => true,
+        }
+    }
+}
+
+/// Identifies a channel in the current pin numbering mode.
+///
+/// `BOARD` and `BCM` channels are numbers, while `TEGRA_SOC` and `CVM` channels are pin names.
+/// Channels are usually not built directly: numbers and strings convert into
+/// a `Channel`, so they can be passed straight to methods such as `GPIO::setup`.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Direction, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::CVM).unwrap();
+/// gpio.setup(vec!["GPIO27"], Direction::OUT, None, None).unwrap();
+/// ```
+#[derive(Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Channel {
+    Number(u32),
+    Name(String),
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Channel::Number(number) => write!(f, "{}", number),
+            Channel::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl From<u32> for Channel {
+    fn from(number: u32) -> Self {
+        Channel::Number(number)
+    }
+}
+
+// integer literals default to i32, so this keeps `vec![7, 11]` working
+impl From<i32> for Channel {
+    fn from(number: i32) -> Self {
+        match u32::try_from(number) {
+            Ok(number) => Channel::Number(number),
+            // no pin has a negative number, the lookup reports it as invalid
+            Err(_) => Channel::Name(number.to_string()),
         }
     }
 }
 
+impl From<&str> for Channel {
+    fn from(name: &str) -> Self {
+        Channel::Name(String::from(name))
+    }
+}
+
+impl From<String> for Channel {
+    fn from(name: String) -> Self {
+        Channel::Name(name)
+    }
+}
+
 static CLARA_AGX_XAVIER: &str = "CLARA_AGX_XAVIER";
 static JETSON_NX: &str = "JETSON_NX";
 static JETSON_XAVIER: &str = "JETSON_XAVIER";
@@ -114,8 +185,10 @@ static JETSON_TX1: &str = "JETSON_TX1";
 static JETSON_NANO: &str = "JETSON_NANO";
 static JETSON_TX2_NX: &str = "JETSON_TX2_NX";
 static JETSON_ORIN: &str = "JETSON_ORIN";
+static JETSON_ORIN_NX: &str = "JETSON_ORIN_NX";
+static JETSON_ORIN_NANO: &str = "JETSON_ORIN_NANO";
 
-static JETSON_MODELS: [&str; 8] = [
+static JETSON_MODELS: [&str; 10] = [
     CLARA_AGX_XAVIER,
     JETSON_NX,
     JETSON_XAVIER,
@@ -124,8 +197,34 @@ static JETSON_MODELS: [&str; 8] = [
     JETSON_NANO,
     JETSON_TX2_NX,
     JETSON_ORIN,
+    JETSON_ORIN_NX,
+    JETSON_ORIN_NANO,
 ];
 
+/// Returns the highest frequency in Hz the PWM controllers of the model can generate.
+///
+/// The Tegra PWM controller divides its clock by 256 to get the duty cycle
+/// resolution, so the output frequency is limited to the controller clock / 256.
+pub(crate) fn pwm_max_frequency(model: &str) -> Option<f64> {
+    let clock_hz: f64 = if model == JETSON_NANO || model == JETSON_TX1 {
+        48_000_000.0
+    } else if model == JETSON_TX2 || model == JETSON_TX2_NX {
+        102_000_000.0
+    } else if model == JETSON_XAVIER
+        || model == JETSON_NX
+        || model == CLARA_AGX_XAVIER
+        || model == JETSON_ORIN
+        || model == JETSON_ORIN_NX
+        || model == JETSON_ORIN_NANO
+    {
+        408_000_000.0
+    } else {
+        return None;
+    };
+
+    Some(clock_hz / 256.0)
+}
+
 /// Contains all relevant GPIO data for each Jetson platform.
 ///
 /// This information is automatically configured during the initialization of the library.
@@ -145,35 +244,95 @@ struct PinDefinition {
     name: HashMap<u32, String>,
     chip_sysfs: String,
     board: u32,
-    bcm: u32,
+    bcm: Option<u32>,
     cvm: String,
     tegra_soc: String,
     pwm_chip_sysfs: Option<String>,
     pwm_id: Option<u32>,
 }
 
+impl PinDefinition {
+    // Returns the key of the `gpio` and `name` tables to use for a chip reporting
+    // `ngpio` lines. Pins defined for a single ngpio are used regardless of it.
+    fn ngpio_key(&self, ngpio: u32) -> Result<u32> {
+        if self.gpio.contains_key(&ngpio) {
+            return Ok(ngpio);
+        }
+        if self.gpio.len() == 1 {
+            return Ok(*self.gpio.keys().next().unwrap());
+        }
+
+        let mut expected: Vec<u32> = self.gpio.keys().copied().collect();
+        expected.sort();
+        let expected: Vec<String> = expected.iter().map(|ngpio| ngpio.to_string()).collect();
+        Err(anyhow!(
+            "No GPIO number for pin {} on {}: expected a chip with ngpio {}, but it reports {}",
+            self.board,
+            self.chip_sysfs,
+            expected.join(" or "),
+            ngpio
+        ))
+    }
+}
+
 /// Contains information about a single GPIO channel.
 ///
 /// This information is automatically gathered during the initialization of the library.
 /// The fields are:
-/// * `channel`: Channel number
+/// * `channel`: Channel number or name
 /// * `gpio_chip_dir`: GPIO chip sysfs directory
-/// * `gpio`: Linux GPIO pin number (within chip, not global)
+/// * `gpio_chip_dev`: GPIO chip character device, e.g. `/dev/gpiochip0`
+/// * `line_offset`: Linux GPIO pin number within its chip (chip-relative offset)
 /// * `global_gpio`: Linux exported GPIO number (global)
 /// * `global_gpio_name`: Linux exported GPIO name
 /// * `pwm_chip_dir`: PWM chip sysfs directory
 /// * `pwm_id`: PWM ID within PWM chip
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChannelInfo {
-    pub channel: u32,
+    pub channel: Channel,
     pub gpio_chip_dir: String,
-    pub gpio: HashMap<u32, u32>,
+    pub gpio_chip_dev: Option<String>,
+    pub line_offset: u32,
     pub global_gpio: u32,
     pub global_gpio_name: String,
     pub pwm_chip_dir: Option<String>,
     pub pwm_id: Option<u32>,
 }
 
+/// Contains every identifier of a single header pin.
+///
+/// The fields are:
+/// * `board`: Pin number (BOARD mode)
+/// * `bcm`: Pin number (BCM mode), `None` if the pin has no BCM equivalent
+/// * `cvm`: Pin name (CVM mode)
+/// * `tegra_soc`: Pin name (TEGRA_SOC mode)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinAliases {
+    pub board: u32,
+    pub bcm: Option<u32>,
+    pub cvm: String,
+    pub tegra_soc: String,
+}
+
+/// Describes a single GPIO pin of the header, as returned by `GPIO::pin_table`.
+///
+/// The fields are:
+/// * `board`: Pin number (BOARD mode)
+/// * `bcm`: Pin number (BCM mode), `None` if the pin has no BCM equivalent
+/// * `cvm`: Pin name (CVM mode)
+/// * `tegra_soc`: Pin name (TEGRA_SOC mode)
+/// * `pwm_capable`: Whether the pin can drive a hardware PWM output
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinMapEntry {
+    pub board: u32,
+    pub bcm: Option<u32>,
+    pub cvm: String,
+    pub tegra_soc: String,
+    pub pwm_capable: bool,
+}
+
 /// Contains information about the Jetson platform.
 ///
 /// This information is automatically gathered during the initialization of the library.
@@ -184,7 +343,11 @@ pub struct ChannelInfo {
 /// * `ttype`: Board type
 /// * `manufacturer`: Board manufacturer
 /// * `processor`: Processor type
+/// * `serial_number`: Module serial number, if exposed by the device tree
+///
+/// The information implements `Display`, printing one field per line.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JetsonInfo {
     pub p1_revision: u32,
     pub ram: String,
@@ -192,79 +355,121 @@ pub struct JetsonInfo {
     pub ttype: String,
     pub manufacturer: String,
     pub processor: String,
+    pub serial_number: Option<String>,
 }
 
-fn read_file_to_string(path: &str) -> String {
-    match fs::read_to_string(path) {
-        Ok(contents) => contents.trim().to_string(),
-        Err(e) => {
-            // error!("Failed to read file {}: {}", path, e);
-            String::from("")
+impl fmt::Display for JetsonInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Type: {}", self.ttype)?;
+        writeln!(f, "Manufacturer: {}", self.manufacturer)?;
+        writeln!(f, "Processor: {}", self.processor)?;
+        writeln!(f, "RAM: {}", self.ram)?;
+        writeln!(f, "Revision: {}", self.revision)?;
+        writeln!(f, "P1 revision: {}", self.p1_revision)?;
+        if let Some(serial_number) = &self.serial_number {
+            writeln!(f, "Serial number: {}", serial_number)?;
         }
+        Ok(())
     }
 }
 
-fn string_to_uint(s: String) -> u32 {
-    match s.parse::<u32>() {
-        Ok(i) => i,
-        Err(e) => {
-            // error!("Failed to parse string to unsigned integer: {}", e);
-            0
-        }
+fn read_file_to_string(path: &str) -> Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.trim().to_string()),
+        Err(e) => Err(anyhow!("failed to read {}: {}", path, e)),
     }
 }
 
-fn find_pmgr_board(prefix: &str) -> Option<String> {
-    let ids_path = "/proc/device-tree/chosen/plugin-manager/ids";
-    let ids_path_k510 = "/proc/device-tree/chosen/ids";
+// Parses the `name` attribute (e.g. `base`) of a GPIO chip, naming the chip and
+// the offending value on failure rather than silently returning 0
+fn string_to_uint(s: &str, name: &str, chip: &str) -> Result<u32> {
+    s.parse::<u32>().map_err(|_| anyhow!("failed to parse {} for chip {}: '{}'", name, chip, s))
+}
+
+fn find_pmgr_board(prefix: &str, warn: &dyn Fn(&str)) -> Result<Option<String>> {
+    let ids_path = device_tree_path("chosen/plugin-manager/ids");
+    let ids_path_k510 = device_tree_path("chosen/ids");
 
-    if Path::new(ids_path).exists() {
-        for f in Path::new(ids_path).read_dir().unwrap() {
-            let f = f.unwrap();
-            let f = f.file_name().into_string().unwrap();
+    if Path::new(&ids_path).exists() {
+        for f in Path::new(&ids_path).read_dir()? {
+            let f = f?.file_name().to_string_lossy().to_string();
             if f.starts_with(prefix) {
-                return Some(f);
+                return Ok(Some(f));
             }
         }
-    } else if Path::new(ids_path_k510).exists() {
-        let mut f = File::open(ids_path_k510).unwrap();
+    } else if Path::new(&ids_path_k510).exists() {
+        let mut f = File::open(&ids_path_k510)?;
         let mut ids = String::new();
-        f.read_to_string(&mut ids).unwrap();
+        f.read_to_string(&mut ids)?;
         for s in ids.split_whitespace() {
             if s.starts_with(prefix) {
-                return Some(s.to_string());
+                return Ok(Some(s.to_string()));
             }
         }
     } else {
-        eprintln!("WARNING: Plugin manager information missing from device tree.");
-        eprintln!("WARNING: Cannot determine whether the expected Jetson board is present.");
+        warn(concat!(
+            "Plugin manager information missing from device tree, ",
+            "cannot determine whether the expected Jetson board is present"
+        ));
     }
 
-    None
+    Ok(None)
 }
 
-fn warn_if_not_carrier_board(carrier_boards: &[&str]) {
+fn warn_if_not_carrier_board(carrier_boards: &[&str], warn: &dyn Fn(&str)) -> Result<()> {
     let mut found = false;
     for b in carrier_boards {
-        found = find_pmgr_board(format!("{}-", b).as_str()).is_some();
+        found = find_pmgr_board(format!("{}-", b).as_str(), warn)?.is_some();
         if found {
             break;
         }
     }
 
     if !found {
-        eprintln!("WARNING: Carrier board is not from a Jetson Developer Kit.");
-        eprintln!("WARNNIG: This library has not been verified with this carrier board,");
-        eprintln!("WARNING: and in fact is unlikely to work correctly.");
+        warn(concat!(
+            "Carrier board is not from a Jetson Developer Kit. This library has not been verified ",
+            "with this carrier board, and in fact is unlikely to work correctly"
+        ));
     }
-}
 
-fn get_model() -> Result<String> {
-    let compatible_path = "/proc/device-tree/compatible";
+    Ok(())
+}
 
+// Returns the model of the board with the given device tree compatible strings,
+// the entries of `/proc/device-tree/compatible`.
+fn model_from_compatible(compats: &[&str]) -> Result<String> {
     let compats_jetson_orins = [
         "nvidia,p3737-0000+p3701-0000",
         "nvidia,p3737-0000+p3701-0004",
+        "nvidia,p3737-0000+p3701-0005",
+        // AGX Orin Industrial
+        "nvidia,p3737-0000+p3701-0008",
+        "nvidia,p3737-0005+p3701-0004",
+        "nvidia,p3737-0005+p3701-0005",
+        "nvidia,p3737-0005+p3701-0008",
+    ];
+
+    let compats_jetson_orins_nx = [
+        "nvidia,p3509-0000+p3767-0000",
+        "nvidia,p3768-0000+p3767-0000",
+        "nvidia,p3509-0000+p3767-0001",
+        "nvidia,p3768-0000+p3767-0001",
+        // Super modes of JetPack 6.2
+        "nvidia,p3768-0000+p3767-0000-super",
+        "nvidia,p3768-0000+p3767-0001-super",
+    ];
+
+    let compats_jetson_orins_nano = [
+        "nvidia,p3509-0000+p3767-0003",
+        "nvidia,p3768-0000+p3767-0003",
+        "nvidia,p3509-0000+p3767-0004",
+        "nvidia,p3768-0000+p3767-0004",
+        "nvidia,p3509-0000+p3767-0005",
+        "nvidia,p3768-0000+p3767-0005",
+        // Orin Nano Super developer kit and Super modes of JetPack 6.2
+        "nvidia,p3768-0000+p3767-0003-super",
+        "nvidia,p3768-0000+p3767-0004-super",
+        "nvidia,p3768-0000+p3767-0005-super",
     ];
 
     let compats_clara_agx_xavier = ["nvidia,e3900-0000+p2888-0004"];
@@ -304,60 +509,79 @@ fn get_model() -> Result<String> {
         "nvidia,jetson-nano",
     ];
 
-    if Path::new(compatible_path).exists() {
-        let mut compats = Vec::new();
-        let mut file = File::open(compatible_path).unwrap();
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).unwrap();
-        for c in contents.split('\x00') {
-            compats.push(c);
+    let matches = |vals: &[&str]| vals.iter().any(|v| compats.contains(v));
+
+    let model = if matches(&compats_jetson_orins) {
+        JETSON_ORIN
+    } else if matches(&compats_jetson_orins_nx) {
+        JETSON_ORIN_NX
+    } else if matches(&compats_jetson_orins_nano) {
+        JETSON_ORIN_NANO
+    } else if matches(&compats_clara_agx_xavier) {
+        CLARA_AGX_XAVIER
+    } else if matches(&compats_nx) {
+        JETSON_NX
+    } else if matches(&compats_xavier) {
+        JETSON_XAVIER
+    } else if matches(&compats_tx2_nx) {
+        JETSON_TX2_NX
+    } else if matches(&compats_tx2) {
+        JETSON_TX2
+    } else if matches(&compats_tx1) {
+        JETSON_TX1
+    } else if matches(&compats_nano) {
+        JETSON_NANO
+    } else {
+        anyhow::bail!("No known Jetson board in the device tree compatible strings: {}", compats.join(", "));
+    };
+
+    Ok(String::from(model))
+}
+
+// Checks the carrier board, and the module revision of the Jetson Nano, of a detected model.
+fn check_board(model: &str, warn: &dyn Fn(&str)) -> Result<()> {
+    if model == JETSON_ORIN {
+        warn_if_not_carrier_board(&["3737", "0000"], warn)
+    } else if model == JETSON_ORIN_NX || model == JETSON_ORIN_NANO {
+        warn_if_not_carrier_board(&["3509", "3768"], warn)
+    } else if model == CLARA_AGX_XAVIER {
+        warn_if_not_carrier_board(&["3900"], warn)
+    } else if model == JETSON_NX {
+        warn_if_not_carrier_board(&["3509", "3449"], warn)
+    } else if model == JETSON_XAVIER {
+        warn_if_not_carrier_board(&["2822"], warn)
+    } else if model == JETSON_TX2_NX {
+        warn_if_not_carrier_board(&["3509"], warn)
+    } else if model == JETSON_TX2 || model == JETSON_TX1 {
+        warn_if_not_carrier_board(&["2597"], warn)
+    } else if model == JETSON_NANO {
+        let module_id = find_pmgr_board("3448", warn)?;
+        if module_id.is_none() {
+            anyhow::bail!("Could not determine Jetson Nano module revision");
         }
 
-        fn matches(vals: &[&str], compats: &Vec<&str>) -> bool {
-            for v in vals {
-                if compats.contains(v) {
-                    return true;
-                }
-            }
-            return false;
+        let module_id = module_id.unwrap();
+        let revision = module_id.split('-').last().unwrap_or_default();
+        // Revision is an ordered string, not a decimal integer
+        if revision < "200" {
+            anyhow::bail!("Jetson Nano module revision must be A02 or later");
         }
 
-        if matches(&compats_jetson_orins, &compats) {
-            warn_if_not_carrier_board(&["3737", "0000"]);
-            return Ok(String::from(JETSON_ORIN));
-        } else if matches(&compats_clara_agx_xavier, &compats) {
-            warn_if_not_carrier_board(&["3900"]);
-            return Ok(String::from(CLARA_AGX_XAVIER));
-        } else if matches(&compats_nx, &compats) {
-            warn_if_not_carrier_board(&["3509", "3449"]);
-            return Ok(String::from(JETSON_NX));
-        } else if matches(&compats_xavier, &compats) {
-            warn_if_not_carrier_board(&["2822"]);
-            return Ok(String::from(JETSON_XAVIER));
-        } else if matches(&compats_tx2_nx, &compats) {
-            warn_if_not_carrier_board(&["3509"]);
-            return Ok(String::from(JETSON_TX2_NX));
-        } else if matches(&compats_tx2, &compats) {
-            warn_if_not_carrier_board(&["2597"]);
-            return Ok(String::from(JETSON_TX2));
-        } else if matches(&compats_tx1, &compats) {
-            warn_if_not_carrier_board(&["2597"]);
-            return Ok(String::from(JETSON_TX1));
-        } else if matches(&compats_nano, &compats) {
-            let module_id = find_pmgr_board(&"3448");
-            if module_id.is_none() {
-                anyhow::bail!("Could not determine Jetson Nano module revision");
-            }
+        warn_if_not_carrier_board(&["3449", "3542"], warn)
+    } else {
+        Ok(())
+    }
+}
 
-            let module_id = module_id.unwrap();
-            let revision = module_id.split('-').last().unwrap();
-            // Revision is an ordered string, not a decimal integer
-            if revision < "200" {
-                anyhow::bail!("Jetson Nano module revision must be A02 or later");
-            }
+fn get_model(warn: &dyn Fn(&str)) -> Result<String> {
+    let compatible_path = device_tree_path("compatible");
 
-            warn_if_not_carrier_board(&["3449", "3542"]);
-            return Ok(String::from(JETSON_NANO));
+    if Path::new(&compatible_path).exists() {
+        let compatible = fs::read_to_string(&compatible_path)?;
+        let compats: Vec<&str> = compatible.split('\x00').filter(|c| !c.is_empty()).collect();
+        if let Ok(model) = model_from_compatible(&compats) {
+            check_board(&model, warn)?;
+            return Ok(model);
         }
     }
 
@@ -369,10 +593,11 @@ fn get_model() -> Result<String> {
         if JETSON_MODELS.contains(&model_name) {
             return Ok(String::from(model_name));
         } else {
-            eprintln!(
-                "Environment variable 'JETSON_MODEL_NAME={}' is invalid.",
-                model_name
-            );
+            warn(&format!(
+                "Environment variable 'JETSON_MODEL_NAME={}' is invalid, expected one of: {}",
+                model_name,
+                JETSON_MODELS.join(", ")
+            ));
         }
     }
 
@@ -380,6 +605,38 @@ fn get_model() -> Result<String> {
     anyhow::bail!("Could not determine Jetson model");
 }
 
+// The RAM and revision of the Jetson AGX Orin module SKUs, keyed by their device tree compatible.
+const JETSON_ORIN_MODULES: [(&str, &str, &str); 4] = [
+    ("p3701-0000", "32768M", "P3701-0000"),
+    ("p3701-0004", "32768M", "P3701-0004"),
+    ("p3701-0005", "65536M", "P3701-0005"),
+    ("p3701-0008", "65536M", "P3701-0008"),
+];
+
+// Narrows the Jetson AGX Orin info down to the module SKU listed in the device tree,
+// either on its own or combined with the carrier board, whichever the carrier uses.
+fn apply_orin_module(jetson_info: &mut JetsonInfo, compatible: &str) {
+    let lists_module = |module: &str| {
+        compatible
+            .split('\x00')
+            .any(|c| c == format!("nvidia,{}", module) || c.ends_with(&format!("+{}", module)))
+    };
+    if let Some((_, ram, revision)) = JETSON_ORIN_MODULES.iter().find(|(module, _, _)| lists_module(module)) {
+        jetson_info.ram = String::from(*ram);
+        jetson_info.revision = String::from(*revision);
+    }
+}
+
+fn get_serial_number() -> Option<String> {
+    let serial_number = read_file_to_string(&device_tree_path("serial-number")).ok()?;
+    let serial_number = serial_number.trim_end_matches('\0');
+    if serial_number.is_empty() {
+        return None;
+    }
+
+    Some(serial_number.to_string())
+}
+
 fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
     let jetson_orin_pin_defs = [
         PinDefinition {
@@ -387,7 +644,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PQ.06"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 7,
-            bcm: 4,
+            bcm: Some(4),
             cvm: String::from("MCLK05"),
             tegra_soc: String::from("GP66"),
             pwm_chip_sysfs: None,
@@ -399,7 +656,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PR.04"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 11,
-            bcm: 17,
+            bcm: Some(17),
             cvm: String::from("UART1_RTS"),
             tegra_soc: String::from("GP72_UART1_RTS_N"),
             pwm_chip_sysfs: None,
@@ -410,7 +667,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PH.07"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 12,
-            bcm: 18,
+            bcm: Some(18),
             cvm: String::from("I2S2_CLK"),
             tegra_soc: String::from("GP122"),
             pwm_chip_sysfs: None,
@@ -421,7 +678,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PR.00"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 13,
-            bcm: 27,
+            bcm: Some(27),
             cvm: String::from("PWM01"),
             tegra_soc: String::from("GP68"),
             pwm_chip_sysfs: None,
@@ -432,7 +689,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PN.01"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 15,
-            bcm: 22,
+            bcm: Some(22),
             cvm: String::from("GPIO27"),
             tegra_soc: String::from("GP88_PWM1"),
             pwm_chip_sysfs: Some(String::from("3280000.pwm")),
@@ -443,7 +700,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(32, String::from("PBB.01"))]),
             chip_sysfs: String::from("c2f0000.gpio"),
             board: 16,
-            bcm: 23,
+            bcm: Some(23),
             cvm: String::from("GPIO08"),
             tegra_soc: String::from("GP26"),
             pwm_chip_sysfs: None,
@@ -454,7 +711,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PH.00"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 18,
-            bcm: 24,
+            bcm: Some(24),
             cvm: String::from("GPIO35"),
             tegra_soc: String::from("GP115"),
             pwm_chip_sysfs: Some(String::from("32c0000.pwm")),
@@ -465,7 +722,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PZ.05"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 19,
-            bcm: 10,
+            bcm: Some(10),
             cvm: String::from("SPI1_MOSI"),
             tegra_soc: String::from("GP49_SPI1_MOSI"),
             pwm_chip_sysfs: None,
@@ -476,7 +733,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PZ.04"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 21,
-            bcm: 9,
+            bcm: Some(9),
             cvm: String::from("SPI1_MISO"),
             tegra_soc: String::from("GP48_SPI1_MISO"),
             pwm_chip_sysfs: None,
@@ -487,7 +744,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PP.04"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 22,
-            bcm: 25,
+            bcm: Some(25),
             cvm: String::from("GPIO17"),
             tegra_soc: String::from("GP56"),
             pwm_chip_sysfs: None,
@@ -498,7 +755,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PZ.03"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 23,
-            bcm: 11,
+            bcm: Some(11),
             cvm: String::from("SPI1_CLK"),
             tegra_soc: String::from("GP47_SPI1_CLK"),
             pwm_chip_sysfs: None,
@@ -509,7 +766,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PZ.06"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 24,
-            bcm: 8,
+            bcm: Some(8),
             cvm: String::from("SPI1_CS0_N"),
             tegra_soc: String::from("GP50_SPI1_CS0_N"),
             pwm_chip_sysfs: None,
@@ -520,7 +777,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PZ.07"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 26,
-            bcm: 7,
+            bcm: Some(7),
             cvm: String::from("SPI1_CS1_N"),
             tegra_soc: String::from("GP51_SPI1_CS1_N"),
             pwm_chip_sysfs: None,
@@ -531,7 +788,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(32, String::from("PAA.01"))]),
             chip_sysfs: String::from("c2f0000.gpio"),
             board: 29,
-            bcm: 5,
+            bcm: Some(5),
             cvm: String::from("CAN0_DIN"),
             tegra_soc: String::from("GP18_CAN0_DIN"),
             pwm_chip_sysfs: None,
@@ -542,7 +799,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(32, String::from("PAA.00"))]),
             chip_sysfs: String::from("c2f0000.gpio"),
             board: 31,
-            bcm: 6,
+            bcm: Some(6),
             cvm: String::from("CAN0_DOUT"),
             tegra_soc: String::from("GP17_CAN0_DOUT"),
             pwm_chip_sysfs: None,
@@ -553,7 +810,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(32, String::from("PBB.00"))]),
             chip_sysfs: String::from("c2f0000.gpio"),
             board: 32,
-            bcm: 12,
+            bcm: Some(12),
             cvm: String::from("GPIO09"),
             tegra_soc: String::from("GP25"),
             pwm_chip_sysfs: None,
@@ -564,7 +821,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(32, String::from("PAA.02"))]),
             chip_sysfs: String::from("c2f0000.gpio"),
             board: 33,
-            bcm: 13,
+            bcm: Some(13),
             cvm: String::from("CAN1_DOUT"),
             tegra_soc: String::from("GP19_CAN1_DOUT"),
             pwm_chip_sysfs: None,
@@ -575,7 +832,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PI.02"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 35,
-            bcm: 19,
+            bcm: Some(19),
             cvm: String::from("I2S2_FS"),
             tegra_soc: String::from("GP125"),
             pwm_chip_sysfs: None,
@@ -586,7 +843,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PR.05"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 36,
-            bcm: 16,
+            bcm: Some(16),
             cvm: String::from("UART1_CTS"),
             tegra_soc: String::from("GP73_UART1_CTS_N"),
             pwm_chip_sysfs: None,
@@ -597,7 +854,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(32, String::from("PAA.03"))]),
             chip_sysfs: String::from("c2f0000.gpio"),
             board: 37,
-            bcm: 26,
+            bcm: Some(26),
             cvm: String::from("CAN1_DIN"),
             tegra_soc: String::from("GP20_CAN1_DIN"),
             pwm_chip_sysfs: None,
@@ -608,7 +865,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PI.01"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 38,
-            bcm: 20,
+            bcm: Some(20),
             cvm: String::from("I2S2_DIN"),
             tegra_soc: String::from("GP124"),
             pwm_chip_sysfs: None,
@@ -619,7 +876,7 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
             name: HashMap::from([(164, String::from("PI.00"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 40,
-            bcm: 21,
+            bcm: Some(21),
             cvm: String::from("I2S2_DOUT"),
             tegra_soc: String::from("GP123"),
             pwm_chip_sysfs: None,
@@ -627,302 +884,1065 @@ fn get_pin_defs(model: &str) -> Result<Vec<PinDefinition>, anyhow::Error> {
         },
     ];
 
-    let clara_agx_xavier_pin_defs: [PinDefinition; 0] = [];
-
-    let jetson_nx_pin_defs = [
+    // Jetson Orin NX and Orin Nano (p3767) modules, whose 40-pin header differs from AGX Orin
+    let jetson_orin_nx_pin_defs = [
         PinDefinition {
-            gpio: HashMap::from([(224, 148), (169, 118)]),
-            name: HashMap::from([(169, String::from("PS.04"))]),
+            gpio: HashMap::from([(164, 144)]),
+            name: HashMap::from([(164, String::from("PAC.06"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 7,
-            bcm: 4,
+            bcm: Some(4),
             cvm: String::from("GPIO09"),
-            tegra_soc: String::from("AUD_MCLK"),
+            tegra_soc: String::from("GP167"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 140), (169, 112)]),
-            name: HashMap::from([(169, String::from("PR.04"))]),
+            gpio: HashMap::from([(164, 112)]),
+            name: HashMap::from([(164, String::from("PR.04"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 11,
-            bcm: 17,
+            bcm: Some(17),
             cvm: String::from("UART1_RTS"),
-            tegra_soc: String::from("UART1_RTS"),
+            tegra_soc: String::from("GP72_UART1_RTS_N"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 157), (169, 127)]),
-            name: HashMap::from([(169, String::from("PT.05"))]),
+            gpio: HashMap::from([(164, 50)]),
+            name: HashMap::from([(164, String::from("PH.07"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 12,
-            bcm: 18,
+            bcm: Some(18),
             cvm: String::from("I2S0_SCLK"),
-            tegra_soc: String::from("DAP5_SCLK"),
+            tegra_soc: String::from("GP122"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 192), (169, 149)]),
-            name: HashMap::from([(169, String::from("PY.00"))]),
+            gpio: HashMap::from([(164, 122)]),
+            name: HashMap::from([(164, String::from("PY.00"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 13,
-            bcm: 27,
+            bcm: Some(27),
             cvm: String::from("SPI1_SCK"),
-            tegra_soc: String::from("SPI3_SCK"),
+            tegra_soc: String::from("GP36_SPI3_CLK"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(40, 20), (30, 16)]),
-            name: HashMap::from([(30, String::from("PCC.04"))]),
-            chip_sysfs: String::from("c2f0000.gpio"),
+            gpio: HashMap::from([(164, 85)]),
+            name: HashMap::from([(164, String::from("PN.01"))]),
+            chip_sysfs: String::from("2200000.gpio"),
             board: 15,
-            bcm: 22,
+            bcm: Some(22),
             cvm: String::from("GPIO12"),
-            tegra_soc: String::from("TOUCH_CLK"),
-            pwm_chip_sysfs: Some(String::from("c340000.pwm")),
+            tegra_soc: String::from("GP88_PWM1"),
+            pwm_chip_sysfs: Some(String::from("3280000.pwm")),
             pwm_id: Some(0),
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 196), (169, 153)]),
-            name: HashMap::from([(169, String::from("PY.04"))]),
+            gpio: HashMap::from([(164, 126)]),
+            name: HashMap::from([(164, String::from("PY.04"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 16,
-            bcm: 23,
+            bcm: Some(23),
             cvm: String::from("SPI1_CS1"),
-            tegra_soc: String::from("SPI3_CS1_N"),
+            tegra_soc: String::from("GP40_SPI3_CS1_N"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 195), (169, 152)]),
-            name: HashMap::from([(169, String::from("PY.03"))]),
+            gpio: HashMap::from([(164, 125)]),
+            name: HashMap::from([(164, String::from("PY.03"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 18,
-            bcm: 24,
+            bcm: Some(24),
             cvm: String::from("SPI1_CS0"),
-            tegra_soc: String::from("SPI3_CS0_N"),
+            tegra_soc: String::from("GP39_SPI3_CS0_N"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 205), (169, 162)]),
-            name: HashMap::from([(169, String::from("PZ.05"))]),
+            gpio: HashMap::from([(164, 135)]),
+            name: HashMap::from([(164, String::from("PZ.05"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 19,
-            bcm: 10,
+            bcm: Some(10),
             cvm: String::from("SPI0_MOSI"),
-            tegra_soc: String::from("SPI1_MOSI"),
+            tegra_soc: String::from("GP49_SPI1_MOSI"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 204), (169, 161)]),
-            name: HashMap::from([(169, String::from("PZ.04"))]),
+            gpio: HashMap::from([(164, 134)]),
+            name: HashMap::from([(164, String::from("PZ.04"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 21,
-            bcm: 9,
+            bcm: Some(9),
             cvm: String::from("SPI0_MISO"),
-            tegra_soc: String::from("SPI1_MISO"),
+            tegra_soc: String::from("GP48_SPI1_MISO"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 193), (169, 150)]),
-            name: HashMap::from([(169, String::from("PY.01"))]),
+            gpio: HashMap::from([(164, 123)]),
+            name: HashMap::from([(164, String::from("PY.01"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 22,
-            bcm: 25,
+            bcm: Some(25),
             cvm: String::from("SPI1_MISO"),
-            tegra_soc: String::from("SPI3_MISO"),
+            tegra_soc: String::from("GP37_SPI3_MISO"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 203), (169, 160)]),
-            name: HashMap::from([(169, String::from("PZ.03"))]),
+            gpio: HashMap::from([(164, 133)]),
+            name: HashMap::from([(164, String::from("PZ.03"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 23,
-            bcm: 11,
+            bcm: Some(11),
             cvm: String::from("SPI0_SCK"),
-            tegra_soc: String::from("SPI1_SCK"),
+            tegra_soc: String::from("GP47_SPI1_CLK"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 206), (169, 163)]),
-            name: HashMap::from([(169, String::from("PZ.06"))]),
+            gpio: HashMap::from([(164, 136)]),
+            name: HashMap::from([(164, String::from("PZ.06"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 24,
-            bcm: 8,
+            bcm: Some(8),
             cvm: String::from("SPI0_CS0"),
-            tegra_soc: String::from("SPI1_CS0_N"),
+            tegra_soc: String::from("GP50_SPI1_CS0_N"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 207), (169, 164)]),
-            name: HashMap::from([(169, String::from("PZ.07"))]),
+            gpio: HashMap::from([(164, 137)]),
+            name: HashMap::from([(164, String::from("PZ.07"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 26,
-            bcm: 7,
+            bcm: Some(7),
             cvm: String::from("SPI0_CS1"),
-            tegra_soc: String::from("SPI1_CS1_N"),
+            tegra_soc: String::from("GP51_SPI1_CS1_N"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 133), (169, 105)]),
-            name: HashMap::from([(169, String::from("PQ.05"))]),
+            gpio: HashMap::from([(164, 105)]),
+            name: HashMap::from([(164, String::from("PQ.05"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 29,
-            bcm: 5,
+            bcm: Some(5),
             cvm: String::from("GPIO01"),
-            tegra_soc: String::from("SOC_GPIO41"),
+            tegra_soc: String::from("GP65"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 134), (169, 106)]),
-            name: HashMap::from([(169, String::from("PQ.06"))]),
+            gpio: HashMap::from([(164, 106)]),
+            name: HashMap::from([(164, String::from("PQ.06"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 31,
-            bcm: 6,
+            bcm: Some(6),
             cvm: String::from("GPIO11"),
-            tegra_soc: String::from("SOC_GPIO42"),
+            tegra_soc: String::from("GP66"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 136), (169, 108)]),
-            name: HashMap::from([(169, String::from("PR.00"))]),
+            gpio: HashMap::from([(164, 41)]),
+            name: HashMap::from([(164, String::from("PG.06"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 32,
-            bcm: 12,
+            bcm: Some(12),
             cvm: String::from("GPIO07"),
-            tegra_soc: String::from("SOC_GPIO44"),
-            pwm_chip_sysfs: Some(String::from("32f0000.pwm")),
+            tegra_soc: String::from("GP113_PWM7"),
+            pwm_chip_sysfs: Some(String::from("32e0000.pwm")),
             pwm_id: Some(0),
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 105), (169, 84)]),
-            name: HashMap::from([(169, String::from("PN.01"))]),
+            gpio: HashMap::from([(164, 43)]),
+            name: HashMap::from([(164, String::from("PH.00"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 33,
-            bcm: 13,
+            bcm: Some(13),
             cvm: String::from("GPIO13"),
-            tegra_soc: String::from("SOC_GPIO54"),
-            pwm_chip_sysfs: Some(String::from("3280000.pwm")),
+            tegra_soc: String::from("GP115"),
+            pwm_chip_sysfs: Some(String::from("32c0000.pwm")),
             pwm_id: Some(0),
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 160), (169, 130)]),
-            name: HashMap::from([(169, String::from("PU.00"))]),
+            gpio: HashMap::from([(164, 53)]),
+            name: HashMap::from([(164, String::from("PI.02"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 35,
-            bcm: 19,
+            bcm: Some(19),
             cvm: String::from("I2S0_FS"),
-            tegra_soc: String::from("DAP5_FS"),
+            tegra_soc: String::from("GP125"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 141), (169, 113)]),
-            name: HashMap::from([(169, String::from("PR.05"))]),
+            gpio: HashMap::from([(164, 113)]),
+            name: HashMap::from([(164, String::from("PR.05"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 36,
-            bcm: 16,
+            bcm: Some(16),
             cvm: String::from("UART1_CTS"),
-            tegra_soc: String::from("UART1_CTS"),
+            tegra_soc: String::from("GP73_UART1_CTS_N"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 194), (169, 151)]),
-            name: HashMap::from([(169, String::from("PY.02"))]),
+            gpio: HashMap::from([(164, 124)]),
+            name: HashMap::from([(164, String::from("PY.02"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 37,
-            bcm: 26,
+            bcm: Some(26),
             cvm: String::from("SPI1_MOSI"),
-            tegra_soc: String::from("SPI3_MOSI"),
+            tegra_soc: String::from("GP38_SPI3_MOSI"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 159), (169, 129)]),
-            name: HashMap::from([(169, String::from("PT.07"))]),
+            gpio: HashMap::from([(164, 52)]),
+            name: HashMap::from([(164, String::from("PI.01"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 38,
-            bcm: 20,
-            cvm: String::from("I2S0_DIN"),
-            tegra_soc: String::from("DAP5_DIN"),
+            bcm: Some(20),
+            cvm: String::from("I2S0_SDIN"),
+            tegra_soc: String::from("GP124"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
         PinDefinition {
-            gpio: HashMap::from([(224, 158), (169, 128)]),
-            name: HashMap::from([(169, String::from("PT.06"))]),
+            gpio: HashMap::from([(164, 51)]),
+            name: HashMap::from([(164, String::from("PI.00"))]),
             chip_sysfs: String::from("2200000.gpio"),
             board: 40,
-            bcm: 21,
-            cvm: String::from("I2S0_DOUT"),
-            tegra_soc: String::from("DAP5_DOUT"),
+            bcm: Some(21),
+            cvm: String::from("I2S0_SDOUT"),
+            tegra_soc: String::from("GP123"),
             pwm_chip_sysfs: None,
             pwm_id: None,
         },
     ];
 
-    let jetson_xavier_pin_defs: [PinDefinition; 0] = [];
-
-    let jetson_tx2_nx_pin_defs: [PinDefinition; 0] = [];
-
-    let jetson_tx2_pin_defs: [PinDefinition; 0] = [];
-
-    let jetson_tx1_pin_defs: [PinDefinition; 0] = [];
-
-    let jetson_nano_pin_defs: [PinDefinition; 0] = [];
-
-    if model == JETSON_ORIN {
-        let pin_defs = jetson_orin_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == CLARA_AGX_XAVIER {
-        let pin_defs = clara_agx_xavier_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == JETSON_NX {
-        let pin_defs = jetson_nx_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == JETSON_XAVIER {
-        let pin_defs = jetson_xavier_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == JETSON_TX2_NX {
-        let pin_defs = jetson_tx2_nx_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == JETSON_TX2 {
-        let pin_defs = jetson_tx2_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == JETSON_TX1 {
-        let pin_defs = jetson_tx1_pin_defs.to_vec();
-        return Ok(pin_defs);
-    } else if model == JETSON_NANO {
-        let pin_defs = jetson_nano_pin_defs.to_vec();
-        return Ok(pin_defs);
-    }
-
-    anyhow::bail!("No pin definitions found for model {}", model)
-}
+    let clara_agx_xavier_pin_defs: [PinDefinition; 0] = [];
 
-fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
-    if model == JETSON_ORIN {
-        let jetson_info = JetsonInfo {
-            p1_revision: 1,
-            ram: String::from("32768M, 65536M"),
-            revision: String::from("Unknown"),
-            ttype: String::from("JETSON_ORIN"),
-            manufacturer: String::from("NVIDIA"),
-            processor: String::from("A78AE"),
-        };
+    let jetson_nx_pin_defs = [
+        PinDefinition {
+            gpio: HashMap::from([(224, 148), (169, 118)]),
+            name: HashMap::from([(169, String::from("PS.04"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 7,
+            bcm: Some(4),
+            cvm: String::from("GPIO09"),
+            tegra_soc: String::from("AUD_MCLK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 140), (169, 112)]),
+            name: HashMap::from([(169, String::from("PR.04"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 11,
+            bcm: Some(17),
+            cvm: String::from("UART1_RTS"),
+            tegra_soc: String::from("UART1_RTS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 157), (169, 127)]),
+            name: HashMap::from([(169, String::from("PT.05"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 12,
+            bcm: Some(18),
+            cvm: String::from("I2S0_SCLK"),
+            tegra_soc: String::from("DAP5_SCLK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 192), (169, 149)]),
+            name: HashMap::from([(169, String::from("PY.00"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 13,
+            bcm: Some(27),
+            cvm: String::from("SPI1_SCK"),
+            tegra_soc: String::from("SPI3_SCK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 20), (30, 16)]),
+            name: HashMap::from([(30, String::from("PCC.04"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 15,
+            bcm: Some(22),
+            cvm: String::from("GPIO12"),
+            tegra_soc: String::from("TOUCH_CLK"),
+            pwm_chip_sysfs: Some(String::from("c340000.pwm")),
+            pwm_id: Some(0),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 196), (169, 153)]),
+            name: HashMap::from([(169, String::from("PY.04"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 16,
+            bcm: Some(23),
+            cvm: String::from("SPI1_CS1"),
+            tegra_soc: String::from("SPI3_CS1_N"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 195), (169, 152)]),
+            name: HashMap::from([(169, String::from("PY.03"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 18,
+            bcm: Some(24),
+            cvm: String::from("SPI1_CS0"),
+            tegra_soc: String::from("SPI3_CS0_N"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 205), (169, 162)]),
+            name: HashMap::from([(169, String::from("PZ.05"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 19,
+            bcm: Some(10),
+            cvm: String::from("SPI0_MOSI"),
+            tegra_soc: String::from("SPI1_MOSI"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 204), (169, 161)]),
+            name: HashMap::from([(169, String::from("PZ.04"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 21,
+            bcm: Some(9),
+            cvm: String::from("SPI0_MISO"),
+            tegra_soc: String::from("SPI1_MISO"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 193), (169, 150)]),
+            name: HashMap::from([(169, String::from("PY.01"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 22,
+            bcm: Some(25),
+            cvm: String::from("SPI1_MISO"),
+            tegra_soc: String::from("SPI3_MISO"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 203), (169, 160)]),
+            name: HashMap::from([(169, String::from("PZ.03"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 23,
+            bcm: Some(11),
+            cvm: String::from("SPI0_SCK"),
+            tegra_soc: String::from("SPI1_SCK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 206), (169, 163)]),
+            name: HashMap::from([(169, String::from("PZ.06"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 24,
+            bcm: Some(8),
+            cvm: String::from("SPI0_CS0"),
+            tegra_soc: String::from("SPI1_CS0_N"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 207), (169, 164)]),
+            name: HashMap::from([(169, String::from("PZ.07"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 26,
+            bcm: Some(7),
+            cvm: String::from("SPI0_CS1"),
+            tegra_soc: String::from("SPI1_CS1_N"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 133), (169, 105)]),
+            name: HashMap::from([(169, String::from("PQ.05"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 29,
+            bcm: Some(5),
+            cvm: String::from("GPIO01"),
+            tegra_soc: String::from("SOC_GPIO41"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 134), (169, 106)]),
+            name: HashMap::from([(169, String::from("PQ.06"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 31,
+            bcm: Some(6),
+            cvm: String::from("GPIO11"),
+            tegra_soc: String::from("SOC_GPIO42"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 136), (169, 108)]),
+            name: HashMap::from([(169, String::from("PR.00"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 32,
+            bcm: Some(12),
+            cvm: String::from("GPIO07"),
+            tegra_soc: String::from("SOC_GPIO44"),
+            pwm_chip_sysfs: Some(String::from("32f0000.pwm")),
+            pwm_id: Some(0),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 105), (169, 84)]),
+            name: HashMap::from([(169, String::from("PN.01"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 33,
+            bcm: Some(13),
+            cvm: String::from("GPIO13"),
+            tegra_soc: String::from("SOC_GPIO54"),
+            pwm_chip_sysfs: Some(String::from("3280000.pwm")),
+            pwm_id: Some(0),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 160), (169, 130)]),
+            name: HashMap::from([(169, String::from("PU.00"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 35,
+            bcm: Some(19),
+            cvm: String::from("I2S0_FS"),
+            tegra_soc: String::from("DAP5_FS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 141), (169, 113)]),
+            name: HashMap::from([(169, String::from("PR.05"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 36,
+            bcm: Some(16),
+            cvm: String::from("UART1_CTS"),
+            tegra_soc: String::from("UART1_CTS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 194), (169, 151)]),
+            name: HashMap::from([(169, String::from("PY.02"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 37,
+            bcm: Some(26),
+            cvm: String::from("SPI1_MOSI"),
+            tegra_soc: String::from("SPI3_MOSI"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 159), (169, 129)]),
+            name: HashMap::from([(169, String::from("PT.07"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 38,
+            bcm: Some(20),
+            cvm: String::from("I2S0_DIN"),
+            tegra_soc: String::from("DAP5_DIN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 158), (169, 128)]),
+            name: HashMap::from([(169, String::from("PT.06"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 40,
+            bcm: Some(21),
+            cvm: String::from("I2S0_DOUT"),
+            tegra_soc: String::from("DAP5_DOUT"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+    ];
+
+    let jetson_xavier_pin_defs = [
+        PinDefinition {
+            gpio: HashMap::from([(224, 134), (169, 106)]),
+            name: HashMap::from([(169, String::from("PQ.06"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 7,
+            bcm: Some(4),
+            cvm: String::from("MCLK05"),
+            tegra_soc: String::from("SOC_GPIO42"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 140), (169, 112)]),
+            name: HashMap::from([(169, String::from("PR.04"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 11,
+            bcm: Some(17),
+            cvm: String::from("UART1_RTS"),
+            tegra_soc: String::from("UART1_RTS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 63), (169, 51)]),
+            name: HashMap::from([(169, String::from("PH.07"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 12,
+            bcm: Some(18),
+            cvm: String::from("I2S2_CLK"),
+            tegra_soc: String::from("DAP2_SCLK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 136), (169, 108)]),
+            name: HashMap::from([(169, String::from("PR.00"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 13,
+            bcm: Some(27),
+            cvm: String::from("PWM01"),
+            tegra_soc: String::from("SOC_GPIO44"),
+            pwm_chip_sysfs: Some(String::from("32f0000.pwm")),
+            pwm_id: Some(0),
+        },
+        // Older versions of L4T don't enable this PWM controller in DT, so this PWM
+        // channel may not be available.
+        PinDefinition {
+            gpio: HashMap::from([(224, 105), (169, 84)]),
+            name: HashMap::from([(169, String::from("PN.01"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 15,
+            bcm: Some(22),
+            cvm: String::from("GPIO27"),
+            tegra_soc: String::from("SOC_GPIO54"),
+            pwm_chip_sysfs: Some(String::from("3280000.pwm")),
+            pwm_id: Some(0),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 8), (30, 8)]),
+            name: HashMap::from([(30, String::from("PBB.00"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 16,
+            bcm: Some(23),
+            cvm: String::from("GPIO8"),
+            tegra_soc: String::from("CAN1_STB"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 56), (169, 44)]),
+            name: HashMap::from([(169, String::from("PH.00"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 18,
+            bcm: Some(24),
+            cvm: String::from("GPIO35"),
+            tegra_soc: String::from("SOC_GPIO12"),
+            pwm_chip_sysfs: Some(String::from("32c0000.pwm")),
+            pwm_id: Some(0),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 205), (169, 162)]),
+            name: HashMap::from([(169, String::from("PZ.05"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 19,
+            bcm: Some(10),
+            cvm: String::from("SPI1_MOSI"),
+            tegra_soc: String::from("SPI1_MOSI"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 204), (169, 161)]),
+            name: HashMap::from([(169, String::from("PZ.04"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 21,
+            bcm: Some(9),
+            cvm: String::from("SPI1_MISO"),
+            tegra_soc: String::from("SPI1_MISO"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 129), (169, 101)]),
+            name: HashMap::from([(169, String::from("PQ.01"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 22,
+            bcm: Some(25),
+            cvm: String::from("GPIO17"),
+            tegra_soc: String::from("SOC_GPIO21"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 203), (169, 160)]),
+            name: HashMap::from([(169, String::from("PZ.03"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 23,
+            bcm: Some(11),
+            cvm: String::from("SPI1_CLK"),
+            tegra_soc: String::from("SPI1_SCK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 206), (169, 163)]),
+            name: HashMap::from([(169, String::from("PZ.06"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 24,
+            bcm: Some(8),
+            cvm: String::from("SPI1_CS0_N"),
+            tegra_soc: String::from("SPI1_CS0_N"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 207), (169, 164)]),
+            name: HashMap::from([(169, String::from("PZ.07"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 26,
+            bcm: Some(7),
+            cvm: String::from("SPI1_CS1_N"),
+            tegra_soc: String::from("SPI1_CS1_N"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 3), (30, 3)]),
+            name: HashMap::from([(30, String::from("PAA.03"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 29,
+            bcm: Some(5),
+            cvm: String::from("CAN0_DIN"),
+            tegra_soc: String::from("CAN0_DIN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 2), (30, 2)]),
+            name: HashMap::from([(30, String::from("PAA.02"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 31,
+            bcm: Some(6),
+            cvm: String::from("CAN0_DOUT"),
+            tegra_soc: String::from("CAN0_DOUT"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 9), (30, 9)]),
+            name: HashMap::from([(30, String::from("PBB.01"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 32,
+            bcm: Some(12),
+            cvm: String::from("GPIO9"),
+            tegra_soc: String::from("CAN1_EN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 0), (30, 0)]),
+            name: HashMap::from([(30, String::from("PAA.00"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 33,
+            bcm: Some(13),
+            cvm: String::from("CAN1_DOUT"),
+            tegra_soc: String::from("CAN1_DOUT"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 66), (169, 54)]),
+            name: HashMap::from([(169, String::from("PI.02"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 35,
+            bcm: Some(19),
+            cvm: String::from("I2S2_FS"),
+            tegra_soc: String::from("DAP2_FS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        // Input-only (due to base board)
+        PinDefinition {
+            gpio: HashMap::from([(224, 141), (169, 113)]),
+            name: HashMap::from([(169, String::from("PR.05"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 36,
+            bcm: Some(16),
+            cvm: String::from("UART1_CTS"),
+            tegra_soc: String::from("UART1_CTS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(40, 1), (30, 1)]),
+            name: HashMap::from([(30, String::from("PAA.01"))]),
+            chip_sysfs: String::from("c2f0000.gpio"),
+            board: 37,
+            bcm: Some(26),
+            cvm: String::from("CAN1_DIN"),
+            tegra_soc: String::from("CAN1_DIN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 65), (169, 53)]),
+            name: HashMap::from([(169, String::from("PI.01"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 38,
+            bcm: Some(20),
+            cvm: String::from("I2S2_DIN"),
+            tegra_soc: String::from("DAP2_DIN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(224, 64), (169, 52)]),
+            name: HashMap::from([(169, String::from("PI.00"))]),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 40,
+            bcm: Some(21),
+            cvm: String::from("I2S2_DOUT"),
+            tegra_soc: String::from("DAP2_DOUT"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+    ];
+
+    let jetson_tx2_nx_pin_defs: [PinDefinition; 0] = [];
+
+    let jetson_tx2_pin_defs: [PinDefinition; 0] = [];
+
+    let jetson_tx1_pin_defs: [PinDefinition; 0] = [];
+
+    let jetson_nano_pin_defs = [
+        PinDefinition {
+            gpio: HashMap::from([(256, 216)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 7,
+            bcm: Some(4),
+            cvm: String::from("GPIO9"),
+            tegra_soc: String::from("AUD_MCLK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 50)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 11,
+            bcm: Some(17),
+            cvm: String::from("UART1_RTS"),
+            tegra_soc: String::from("UART2_RTS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 79)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 12,
+            bcm: Some(18),
+            cvm: String::from("I2S0_SCLK"),
+            tegra_soc: String::from("DAP4_SCLK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 14)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 13,
+            bcm: Some(27),
+            cvm: String::from("SPI1_SCK"),
+            tegra_soc: String::from("SPI2_SCK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 194)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 15,
+            bcm: Some(22),
+            cvm: String::from("GPIO12"),
+            tegra_soc: String::from("LCD_TE"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 232)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 16,
+            bcm: Some(23),
+            cvm: String::from("SPI1_CS1"),
+            tegra_soc: String::from("SPI2_CS1"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 15)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 18,
+            bcm: Some(24),
+            cvm: String::from("SPI1_CS0"),
+            tegra_soc: String::from("SPI2_CS0"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 16)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 19,
+            bcm: Some(10),
+            cvm: String::from("SPI0_MOSI"),
+            tegra_soc: String::from("SPI1_MOSI"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 17)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 21,
+            bcm: Some(9),
+            cvm: String::from("SPI0_MISO"),
+            tegra_soc: String::from("SPI1_MISO"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 13)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 22,
+            bcm: Some(25),
+            cvm: String::from("SPI1_MISO"),
+            tegra_soc: String::from("SPI2_MISO"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 18)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 23,
+            bcm: Some(11),
+            cvm: String::from("SPI0_SCK"),
+            tegra_soc: String::from("SPI1_SCK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 19)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 24,
+            bcm: Some(8),
+            cvm: String::from("SPI0_CS0"),
+            tegra_soc: String::from("SPI1_CS0"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 20)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 26,
+            bcm: Some(7),
+            cvm: String::from("SPI0_CS1"),
+            tegra_soc: String::from("SPI1_CS1"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 149)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 29,
+            bcm: Some(5),
+            cvm: String::from("GPIO01"),
+            tegra_soc: String::from("CAM_AF_EN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 200)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 31,
+            bcm: Some(6),
+            cvm: String::from("GPIO11"),
+            tegra_soc: String::from("GPIO_PZ0"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        // Older versions of L4T have a DT bug which instantiates a bogus device
+        // which prevents this library from using this PWM channel.
+        PinDefinition {
+            gpio: HashMap::from([(256, 168)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 32,
+            bcm: Some(12),
+            cvm: String::from("GPIO07"),
+            tegra_soc: String::from("LCD_BL_PW"),
+            pwm_chip_sysfs: Some(String::from("7000a000.pwm")),
+            pwm_id: Some(0),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 38)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 33,
+            bcm: Some(13),
+            cvm: String::from("GPIO13"),
+            tegra_soc: String::from("GPIO_PE6"),
+            pwm_chip_sysfs: Some(String::from("7000a000.pwm")),
+            pwm_id: Some(2),
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 76)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 35,
+            bcm: Some(19),
+            cvm: String::from("I2S0_FS"),
+            tegra_soc: String::from("DAP4_FS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 51)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 36,
+            bcm: Some(16),
+            cvm: String::from("UART1_CTS"),
+            tegra_soc: String::from("UART2_CTS"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 12)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 37,
+            bcm: Some(26),
+            cvm: String::from("SPI1_MOSI"),
+            tegra_soc: String::from("SPI2_MOSI"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 77)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 38,
+            bcm: Some(20),
+            cvm: String::from("I2S0_DIN"),
+            tegra_soc: String::from("DAP4_DIN"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+        PinDefinition {
+            gpio: HashMap::from([(256, 78)]),
+            name: HashMap::new(),
+            chip_sysfs: String::from("6000d000.gpio"),
+            board: 40,
+            bcm: Some(21),
+            cvm: String::from("I2S0_DOUT"),
+            tegra_soc: String::from("DAP4_DOUT"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        },
+    ];
+
+    if model == JETSON_ORIN {
+        let pin_defs = jetson_orin_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_ORIN_NX || model == JETSON_ORIN_NANO {
+        let pin_defs = jetson_orin_nx_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == CLARA_AGX_XAVIER {
+        let pin_defs = clara_agx_xavier_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_NX {
+        let pin_defs = jetson_nx_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_XAVIER {
+        let pin_defs = jetson_xavier_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_TX2_NX {
+        let pin_defs = jetson_tx2_nx_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_TX2 {
+        let pin_defs = jetson_tx2_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_TX1 {
+        let pin_defs = jetson_tx1_pin_defs.to_vec();
+        return Ok(pin_defs);
+    } else if model == JETSON_NANO {
+        let pin_defs = jetson_nano_pin_defs.to_vec();
+        return Ok(pin_defs);
+    }
+
+    anyhow::bail!("No pin definitions found for model {}", model)
+}
+
+fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
+    if model == JETSON_ORIN {
+        let jetson_info = JetsonInfo {
+            p1_revision: 1,
+            ram: String::from("32768M, 65536M"),
+            revision: String::from("Unknown"),
+            ttype: String::from("JETSON_ORIN"),
+            manufacturer: String::from("NVIDIA"),
+            processor: String::from("A78AE"),
+            serial_number: None,
+        };
+        return Ok(jetson_info);
+    } else if model == JETSON_ORIN_NX {
+        let jetson_info = JetsonInfo {
+            p1_revision: 1,
+            ram: String::from("16384M, 8192M"),
+            revision: String::from("Unknown"),
+            ttype: String::from("JETSON_ORIN_NX"),
+            manufacturer: String::from("NVIDIA"),
+            processor: String::from("A78AE"),
+            serial_number: None,
+        };
+        return Ok(jetson_info);
+    } else if model == JETSON_ORIN_NANO {
+        let jetson_info = JetsonInfo {
+            p1_revision: 1,
+            ram: String::from("8192M, 4096M"),
+            revision: String::from("Unknown"),
+            ttype: String::from("JETSON_ORIN_NANO"),
+            manufacturer: String::from("NVIDIA"),
+            processor: String::from("A78AE"),
+            serial_number: None,
+        };
         return Ok(jetson_info);
     } else if model == CLARA_AGX_XAVIER {
         let jetson_info = JetsonInfo {
@@ -932,6 +1952,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("CLARA_AGX_XAVIER"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM Carmel"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     } else if model == JETSON_NX {
@@ -942,6 +1963,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("Jetson NX"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM Carmel"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     } else if model == JETSON_XAVIER {
@@ -952,6 +1974,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("Jetson Xavier"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM Carmel"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     } else if model == JETSON_TX2_NX {
@@ -962,6 +1985,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("Jetson TX2 NX"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM A57 + Denver"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     } else if model == JETSON_TX2 {
@@ -972,6 +1996,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("Jetson TX2"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM A57 + Denver"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     } else if model == JETSON_TX1 {
@@ -982,6 +2007,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("Jetson TX1"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM A57"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     } else if model == JETSON_NANO {
@@ -992,6 +2018,7 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
             ttype: String::from("Jetson Nano"),
             manufacturer: String::from("NVIDIA"),
             processor: String::from("ARM A57"),
+            serial_number: None,
         };
         return Ok(jetson_info);
     }
@@ -999,22 +2026,112 @@ fn get_jetson_info(model: &str) -> Result<JetsonInfo, anyhow::Error> {
     anyhow::bail!("No info found for model {}", model)
 }
 
-pub(crate) fn get_data() -> (
+// GPIO chip sysfs name, base, and ngpio
+pub(crate) type GpioChip = (String, u32, u32);
+
+pub(crate) type ChannelDataByMode = HashMap<Mode, HashMap<Channel, ChannelInfo>>;
+
+pub(crate) type BoardData = (
     String,
     JetsonInfo,
-    HashMap<Mode, HashMap<u32, ChannelInfo>>,
-) {
-    let model = get_model().unwrap();
+    ChannelDataByMode,
+    Vec<GpioChip>,
+    Vec<PinAliases>,
+);
+
+// The GPIO and PWM chips of the pin definitions, keyed by their sysfs names.
+#[derive(Default)]
+struct ResolvedChips {
+    gpio_chip_dirs: HashMap<String, String>,
+    gpio_chip_devs: HashMap<String, String>,
+    gpio_chip_base: HashMap<String, u32>,
+    gpio_chip_ngpio: HashMap<String, u32>,
+    pwm_dirs: HashMap<String, String>,
+}
+
+pub(crate) fn get_data(warn: &dyn Fn(&str)) -> Result<BoardData> {
+    let model = get_model(warn)?;
+    get_data_for_model(&model, warn)
+}
+
+// Checks that the model is one of the supported Jetson models.
+fn check_model(model: &str) -> Result<()> {
+    if !JETSON_MODELS.contains(&model) {
+        anyhow::bail!(
+            "Invalid Jetson model '{}', expected one of: {}",
+            model,
+            JETSON_MODELS.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the board data of the given model instead of detecting the model.
+pub(crate) fn get_data_for_model(model: &str, warn: &dyn Fn(&str)) -> Result<BoardData> {
+    check_model(model)?;
+    let model = String::from(model);
+
+    let pin_defs: Vec<PinDefinition> = get_pin_defs(model.as_str())?;
+    let mut jetson_info: JetsonInfo = get_jetson_info(model.as_str())?;
+    jetson_info.serial_number = get_serial_number();
+    if model == JETSON_ORIN {
+        let compatible = read_file_to_string(&device_tree_path("compatible")).unwrap_or_default();
+        apply_orin_module(&mut jetson_info, &compatible);
+    }
+
+    let chips = resolve_chips(&pin_defs, warn)?;
+    build_data(model, jetson_info, pin_defs, chips, warn)
+}
+
+/// Returns the board data of the mock backend, which needs no GPIO chips.
+///
+/// Unless a model is given, it is detected as usual, falling back to the Jetson
+/// Nano when not running on a Jetson board. Every GPIO chip gets a made-up base,
+/// so each pin has a distinct global GPIO number. PWM chips are not resolved.
+pub(crate) fn get_mock_data(model: Option<&str>, warn: &dyn Fn(&str)) -> Result<BoardData> {
+    let model = match model {
+        Some(model) => {
+            check_model(model)?;
+            String::from(model)
+        }
+        None => get_model(warn).unwrap_or_else(|_| String::from(JETSON_NANO)),
+    };
+
+    let pin_defs: Vec<PinDefinition> = get_pin_defs(model.as_str())?;
+    let jetson_info: JetsonInfo = get_jetson_info(model.as_str())?;
+
+    let mut chips = ResolvedChips::default();
+    let mut base = 0;
+    for pin_def in pin_defs.iter() {
+        if pin_def.chip_sysfs.is_empty() || chips.gpio_chip_base.contains_key(&pin_def.chip_sysfs) {
+            continue;
+        }
+        let Some(ngpio) = pin_def.gpio.keys().min() else {
+            continue;
+        };
 
-    let pin_defs: Vec<PinDefinition> = get_pin_defs(model.as_str()).unwrap();
-    let jetson_info: JetsonInfo = get_jetson_info(model.as_str()).unwrap();
+        let name = pin_def.chip_sysfs.clone();
+        chips.gpio_chip_dirs.insert(name.clone(), format!("{}/devices/{}", paths().sysfs, name));
+        chips.gpio_chip_base.insert(name.clone(), base);
+        chips.gpio_chip_ngpio.insert(name, *ngpio);
+        base += ngpio;
+    }
+
+    build_data(model, jetson_info, pin_defs, chips, warn)
+}
 
+// Finds the sysfs directories, character devices, base, and ngpio values of the
+// GPIO chips and the sysfs directories of the PWM chips used by the pins.
+fn resolve_chips(pin_defs: &[PinDefinition], warn: &dyn Fn(&str)) -> Result<ResolvedChips> {
     let mut gpio_chip_dirs: HashMap<String, String> = HashMap::new();
+    let mut gpio_chip_devs: HashMap<String, String> = HashMap::new();
     let mut gpio_chip_base: HashMap<String, u32> = HashMap::new();
     let mut gpio_chip_ngpio: HashMap<String, u32> = HashMap::new();
     let mut pwm_dirs: HashMap<String, String> = HashMap::new();
 
-    let sysfs_prefixes = ["/sys/devices/", "/sys/devices/platform/"];
+    let sysfs = paths().sysfs;
+    let sysfs_prefixes = [format!("{}/devices/", sysfs), format!("{}/devices/platform/", sysfs)];
 
     // create an array of unique chip_sysfs values from the pin definitions
     let mut gpio_chip_names: Vec<String> = Vec::new();
@@ -1035,27 +2152,53 @@ pub(crate) fn get_data() -> (
             }
         }
 
-        if gpio_chip_dir == "" {
-            // anyhow::bail!("Cannot find GPIO chip {}", gpio_chip_name);
+        if gpio_chip_dir.is_empty() {
+            warn(&format!("Cannot find GPIO chip {}", gpio_chip_name));
+            continue;
+        }
+
+        let gpio_chip_dev = find_gpio_chip_dev(&gpio_chip_dir);
+        if let Some(gpio_chip_dev) = gpio_chip_dev.as_ref() {
+            gpio_chip_devs.insert(gpio_chip_name.clone(), gpio_chip_dev.clone());
         }
 
-        gpio_chip_dirs.insert(gpio_chip_name.clone(), gpio_chip_dir.clone());
-        let gpio_chip_gpio_dir = gpio_chip_dir + "/gpio";
+        let gpio_chip_gpio_dir = gpio_chip_dir.clone() + "/gpio";
+        let entries = match fs::read_dir(&gpio_chip_gpio_dir) {
+            Ok(entries) => entries,
+            Err(_) => {
+                // kernels without the sysfs GPIO class only expose the character
+                // device, which has no global base
+                match gpio_chip_dev.map(|gpio_chip_dev| chip_lines(&gpio_chip_dev)) {
+                    Some(Ok(ngpio)) => {
+                        gpio_chip_dirs.insert(gpio_chip_name.clone(), gpio_chip_dir.clone());
+                        gpio_chip_base.insert(gpio_chip_name.clone(), 0);
+                        gpio_chip_ngpio.insert(gpio_chip_name.clone(), ngpio);
+                    }
+                    _ => warn(&format!("Cannot list {}", gpio_chip_gpio_dir)),
+                }
+                continue;
+            }
+        };
+
         // for each file in the directory
-        for entry in fs::read_dir(&gpio_chip_gpio_dir).unwrap() {
-            let entry = entry.unwrap();
-            let path = entry.path();
-            let file_name = path.file_name().unwrap().to_str().unwrap();
+        for entry in entries {
+            let file_name = entry?.file_name();
+            let file_name = file_name.to_string_lossy();
             // check if the file name starts with "gpiochip"
             if !file_name.starts_with("gpiochip") {
                 continue;
             }
+            // The global GPIO numbers are derived from the base, so a chip whose
+            // base or ngpio can't be read must not be mapped with a made up value
             let base_fn = format!("{}/{}/base", gpio_chip_gpio_dir, file_name);
-            let base = string_to_uint(read_file_to_string(&base_fn));
-            gpio_chip_base.insert(gpio_chip_name.clone(), base);
-
+            let base = read_file_to_string(&base_fn)?;
             let ngpio_fn = format!("{}/{}/ngpio", gpio_chip_gpio_dir, file_name);
-            let ngpio = string_to_uint(read_file_to_string(&ngpio_fn));
+            let ngpio = read_file_to_string(&ngpio_fn)?;
+
+            gpio_chip_dirs.insert(gpio_chip_name.clone(), gpio_chip_dir.clone());
+            let base = string_to_uint(&base, "base", gpio_chip_name)?;
+            let ngpio = string_to_uint(&ngpio, "ngpio", gpio_chip_name)?;
+            gpio_chip_base.insert(gpio_chip_name.clone(), base);
             gpio_chip_ngpio.insert(gpio_chip_name.clone(), ngpio);
 
             break;
@@ -1095,10 +2238,9 @@ pub(crate) fn get_data() -> (
         }
 
         // for each file in the directory
-        for entry in fs::read_dir(&pwm_chip_pwm_dir).unwrap() {
-            let entry = entry.unwrap();
-            let path = entry.path();
-            let file_name = path.file_name().unwrap().to_str().unwrap();
+        for entry in fs::read_dir(&pwm_chip_pwm_dir)? {
+            let file_name = entry?.file_name();
+            let file_name = file_name.to_string_lossy();
             // check if the file name starts with "gpiochip"
             if !file_name.starts_with("pwmchip") {
                 continue;
@@ -1111,49 +2253,260 @@ pub(crate) fn get_data() -> (
         }
     }
 
+    Ok(ResolvedChips {
+        gpio_chip_dirs,
+        gpio_chip_devs,
+        gpio_chip_base,
+        gpio_chip_ngpio,
+        pwm_dirs,
+    })
+}
+
+fn build_data(
+    model: String,
+    jetson_info: JetsonInfo,
+    pin_defs: Vec<PinDefinition>,
+    chips: ResolvedChips,
+    warn: &dyn Fn(&str),
+) -> Result<BoardData> {
+    let ResolvedChips {
+        gpio_chip_dirs,
+        gpio_chip_devs,
+        gpio_chip_base,
+        gpio_chip_ngpio,
+        pwm_dirs,
+    } = chips;
+
     // create a hashmap of channel info, mapping each GPIO pin to a ChannelInfo struct
-    let mut board_data: HashMap<u32, ChannelInfo> = HashMap::new();
-    let mut bcm_data: HashMap<u32, ChannelInfo> = HashMap::new();
+    let mut board_data: HashMap<Channel, ChannelInfo> = HashMap::new();
+    let mut bcm_data: HashMap<Channel, ChannelInfo> = HashMap::new();
+    let mut tegra_soc_data: HashMap<Channel, ChannelInfo> = HashMap::new();
+    let mut cvm_data: HashMap<Channel, ChannelInfo> = HashMap::new();
     for pin_def in pin_defs.iter() {
-        let ngpio = gpio_chip_ngpio.get(&pin_def.chip_sysfs).unwrap();
-        let chip_relative_id = pin_def.gpio.get(ngpio).unwrap();
-        let gpio = gpio_chip_base.get(&pin_def.chip_sysfs).unwrap() + chip_relative_id;
-        let default_gpio_name = format!("gpio{}", gpio);
-        let gpio_name = pin_def.name.get(ngpio).unwrap_or(&default_gpio_name);
+        // pins whose chip could not be resolved keep an empty chip directory, so
+        // they are rejected as "not a GPIO" instead of mapping to a bogus global gpio
+        let (gpio_chip_dir, line_offset, gpio, gpio_name) = match (
+            gpio_chip_dirs.get(&pin_def.chip_sysfs),
+            gpio_chip_base.get(&pin_def.chip_sysfs),
+            gpio_chip_ngpio.get(&pin_def.chip_sysfs),
+        ) {
+            (Some(gpio_chip_dir), Some(base), Some(ngpio)) => {
+                let ngpio = pin_def.ngpio_key(*ngpio)?;
+                let chip_relative_id = &pin_def.gpio[&ngpio];
+                let gpio = base + chip_relative_id;
+                let gpio_name = match pin_def.name.get(&ngpio) {
+                    Some(name) => name.clone(),
+                    None => format!("gpio{}", gpio),
+                };
+                (gpio_chip_dir.clone(), *chip_relative_id, gpio, gpio_name)
+            }
+            _ => {
+                warn(&format!(
+                    "GPIO chip {} could not be resolved, pin {} cannot be used as a GPIO",
+                    pin_def.chip_sysfs, pin_def.board
+                ));
+                (String::new(), 0, 0, String::new())
+            }
+        };
+
+        let gpio_chip_dev = gpio_chip_devs.get(&pin_def.chip_sysfs).cloned();
 
         let mut pwm_chip_dir: Option<String> = None;
-        if pin_def.pwm_chip_sysfs.is_some() {
-            let pwm_chip_sysfs = pin_def.pwm_chip_sysfs.as_ref().unwrap();
+        if let Some(pwm_chip_sysfs) = pin_def.pwm_chip_sysfs.as_ref() {
             pwm_chip_dir = pwm_dirs.get(pwm_chip_sysfs).cloned();
         }
 
         let channel_board = ChannelInfo {
-            channel: pin_def.board.clone(),
-            gpio_chip_dir: gpio_chip_dirs.get(&pin_def.chip_sysfs).unwrap().clone(),
-            gpio: pin_def.gpio.clone(),
-            global_gpio: gpio.clone(),
+            channel: Channel::Number(pin_def.board),
+            gpio_chip_dir: gpio_chip_dir.clone(),
+            gpio_chip_dev: gpio_chip_dev.clone(),
+            line_offset,
+            global_gpio: gpio,
             global_gpio_name: gpio_name.clone(),
             pwm_chip_dir: pwm_chip_dir.clone(),
-            pwm_id: pin_def.pwm_id.clone(),
+            pwm_id: pin_def.pwm_id,
         };
 
-        let channel_bcm = ChannelInfo {
-            channel: pin_def.bcm.clone(),
-            gpio_chip_dir: gpio_chip_dirs.get(&pin_def.chip_sysfs).unwrap().clone(),
-            gpio: pin_def.gpio.clone(),
-            global_gpio: gpio.clone(),
+        // pins without a BCM equivalent are left out of the BCM mode
+        let channel_bcm = pin_def.bcm.map(|bcm| ChannelInfo {
+            channel: Channel::Number(bcm),
+            gpio_chip_dir: gpio_chip_dir.clone(),
+            gpio_chip_dev: gpio_chip_dev.clone(),
+            line_offset,
+            global_gpio: gpio,
+            global_gpio_name: gpio_name.clone(),
+            pwm_chip_dir: pwm_chip_dir.clone(),
+            pwm_id: pin_def.pwm_id,
+        });
+
+        let channel_tegra_soc = ChannelInfo {
+            channel: Channel::Name(pin_def.tegra_soc.clone()),
+            gpio_chip_dir: gpio_chip_dir.clone(),
+            gpio_chip_dev: gpio_chip_dev.clone(),
+            line_offset,
+            global_gpio: gpio,
             global_gpio_name: gpio_name.clone(),
             pwm_chip_dir: pwm_chip_dir.clone(),
-            pwm_id: pin_def.pwm_id.clone(),
+            pwm_id: pin_def.pwm_id,
         };
 
-        board_data.insert(channel_board.channel, channel_board);
-        bcm_data.insert(channel_bcm.channel, channel_bcm);
+        let channel_cvm = ChannelInfo {
+            channel: Channel::Name(pin_def.cvm.clone()),
+            gpio_chip_dir,
+            gpio_chip_dev,
+            line_offset,
+            global_gpio: gpio,
+            global_gpio_name: gpio_name,
+            pwm_chip_dir,
+            pwm_id: pin_def.pwm_id,
+        };
+
+        board_data.insert(channel_board.channel.clone(), channel_board);
+        if let Some(channel_bcm) = channel_bcm {
+            bcm_data.insert(channel_bcm.channel.clone(), channel_bcm);
+        }
+        tegra_soc_data.insert(channel_tegra_soc.channel.clone(), channel_tegra_soc);
+        cvm_data.insert(channel_cvm.channel.clone(), channel_cvm);
     }
 
-    let mut channel_data: HashMap<Mode, HashMap<u32, ChannelInfo>> = HashMap::new();
+    let mut channel_data: ChannelDataByMode = HashMap::new();
     channel_data.insert(Mode::BOARD, board_data);
     channel_data.insert(Mode::BCM, bcm_data);
+    channel_data.insert(Mode::TEGRA_SOC, tegra_soc_data);
+    channel_data.insert(Mode::CVM, cvm_data);
+
+    // keep the resolved chips (sysfs name, base, ngpio) around for diagnostics
+    let mut gpio_chips: Vec<GpioChip> = Vec::new();
+    for (gpio_chip_name, base) in gpio_chip_base.iter() {
+        if let Some(ngpio) = gpio_chip_ngpio.get(gpio_chip_name) {
+            gpio_chips.push((gpio_chip_name.clone(), *base, *ngpio));
+        }
+    }
+    gpio_chips.sort();
+
+    let pin_aliases: Vec<PinAliases> = pin_defs
+        .iter()
+        .map(|pin_def| PinAliases {
+            board: pin_def.board,
+            bcm: pin_def.bcm,
+            cvm: pin_def.cvm.clone(),
+            tegra_soc: pin_def.tegra_soc.clone(),
+        })
+        .collect();
+
+    Ok((model, jetson_info, channel_data, gpio_chips, pin_aliases))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn read_errors_name_the_path() {
+        let err = read_file_to_string("/nonexistent/gpiochip0/base").unwrap_err();
+        assert!(err.to_string().starts_with("failed to read /nonexistent/gpiochip0/base: "));
+    }
+
+    #[test]
+    fn chip_attribute_parse_errors_name_the_chip() {
+        assert_eq!(string_to_uint("348", "base", "2200000.gpio").unwrap(), 348);
+
+        let err = string_to_uint("garbage", "ngpio", "2200000.gpio").unwrap_err();
+        assert_eq!(err.to_string(), "failed to parse ngpio for chip 2200000.gpio: 'garbage'");
+    }
+
+    fn pin_def(gpio: &[(u32, u32)]) -> PinDefinition {
+        PinDefinition {
+            gpio: gpio.iter().copied().collect(),
+            name: HashMap::new(),
+            chip_sysfs: String::from("2200000.gpio"),
+            board: 7,
+            bcm: Some(4),
+            cvm: String::from("GPIO09"),
+            tegra_soc: String::from("AUD_MCLK"),
+            pwm_chip_sysfs: None,
+            pwm_id: None,
+        }
+    }
+
+    #[test]
+    fn ngpio_key_falls_back_to_single_entry() {
+        let dual = pin_def(&[(224, 106), (169, 84)]);
+        assert_eq!(dual.ngpio_key(169).unwrap(), 169);
+        let err = dual.ngpio_key(200).unwrap_err().to_string();
+        assert!(err.contains("169 or 224") && err.contains("200"));
+
+        let single = pin_def(&[(164, 144)]);
+        assert_eq!(single.ngpio_key(200).unwrap(), 164);
+    }
+
+    #[test]
+    fn pins_without_bcm_number_are_left_out_of_bcm_mode() {
+        let mut no_bcm = pin_def(&[(164, 144)]);
+        no_bcm.board = 11;
+        no_bcm.bcm = None;
+        let pin_defs = vec![pin_def(&[(164, 106)]), no_bcm];
+        let jetson_info = get_jetson_info(JETSON_ORIN).unwrap();
+
+        let warnings = RefCell::new(Vec::new());
+        let warn = |message: &str| warnings.borrow_mut().push(message.to_string());
+
+        let (_, _, channel_data, _, pin_aliases) =
+            build_data(String::from(JETSON_ORIN), jetson_info, pin_defs, ResolvedChips::default(), &warn).unwrap();
+        assert_eq!(channel_data[&Mode::BOARD].len(), 2);
+        // neither chip was resolved, which is reported once per pin
+        assert_eq!(warnings.borrow().len(), 2);
+        assert!(warnings.borrow()[0].contains("2200000.gpio could not be resolved"));
+        assert_eq!(channel_data[&Mode::BCM].len(), 1);
+        assert!(channel_data[&Mode::BCM].contains_key(&Channel::Number(4)));
+        assert_eq!(pin_aliases[1].bcm, None);
+    }
+
+    #[test]
+    fn orin_nano_super_is_detected() {
+        let compats = ["nvidia,p3768-0000+p3767-0005-super", "nvidia,p3767-0005", "nvidia,tegra234"];
+        assert_eq!(model_from_compatible(&compats).unwrap(), JETSON_ORIN_NANO);
+        let compats = ["nvidia,p3768-0000+p3767-0001-super", "nvidia,p3767-0001", "nvidia,tegra234"];
+        assert_eq!(model_from_compatible(&compats).unwrap(), JETSON_ORIN_NX);
+    }
+
+    #[test]
+    fn model_is_detected_from_compatible_strings() {
+        let boards = [
+            (JETSON_ORIN, ["nvidia,p3737-0000+p3701-0004", "nvidia,p3701-0004", "nvidia,tegra234"]),
+            (JETSON_ORIN, ["nvidia,p3737-0000+p3701-0008", "nvidia,p3701-0008", "nvidia,tegra234"]),
+            (JETSON_ORIN_NX, ["nvidia,p3768-0000+p3767-0000", "nvidia,p3767-0000", "nvidia,tegra234"]),
+            (JETSON_ORIN_NANO, ["nvidia,p3768-0000+p3767-0005", "nvidia,p3767-0005", "nvidia,tegra234"]),
+            (CLARA_AGX_XAVIER, ["nvidia,e3900-0000+p2888-0004", "nvidia,p2888-0004", "nvidia,tegra194"]),
+            (JETSON_NX, ["nvidia,p3449-0000+p3668-0001", "nvidia,p3668-0001", "nvidia,tegra194"]),
+            (JETSON_XAVIER, ["nvidia,p2972-0000", "nvidia,galen", "nvidia,tegra194"]),
+            (JETSON_TX2_NX, ["nvidia,p3509-0000+p3636-0001", "nvidia,p3636-0001", "nvidia,tegra186"]),
+            (JETSON_TX2, ["nvidia,p2771-0000", "nvidia,quill", "nvidia,tegra186"]),
+            (JETSON_TX1, ["nvidia,p2371-2180", "nvidia,jetson-cv", "nvidia,tegra210"]),
+            (JETSON_NANO, ["nvidia,p3450-0000", "nvidia,jetson-nano", "nvidia,tegra210"]),
+        ];
+        for (model, compats) in boards {
+            assert_eq!(model_from_compatible(&compats).unwrap(), model);
+        }
+
+        let err = model_from_compatible(&["raspberrypi,4-model-b", "brcm,bcm2711"]).unwrap_err();
+        assert!(err.to_string().contains("raspberrypi,4-model-b"));
+    }
+
+    #[test]
+    fn orin_module_sets_ram_and_revision() {
+        let mut jetson_info = get_jetson_info(JETSON_ORIN).unwrap();
+        apply_orin_module(&mut jetson_info, "nvidia,p3737-0000+p3701-0005\x00nvidia,tegra234\x00");
+        assert_eq!(jetson_info.ram, "65536M");
+        assert_eq!(jetson_info.revision, "P3701-0005");
 
-    (model, jetson_info, channel_data)
+        let mut jetson_info = get_jetson_info(JETSON_ORIN).unwrap();
+        apply_orin_module(&mut jetson_info, "nvidia,p3737-0005+p3701-0008\x00nvidia,tegra234\x00");
+        assert_eq!(jetson_info.revision, "P3701-0008");
+
+        let mut jetson_info = get_jetson_info(JETSON_ORIN).unwrap();
+        apply_orin_module(&mut jetson_info, "nvidia,tegra234\x00");
+        assert_eq!(jetson_info.ram, "32768M, 65536M");
+    }
 }
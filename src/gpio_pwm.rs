@@ -0,0 +1,463 @@
+use anyhow::Error;
+use std::{fs, io::Write, path::Path, thread, time::{Duration, Instant}};
+
+use crate::gpio::WarningHandler;
+use crate::gpio_pin_data::ChannelInfo;
+
+/// Specifies what happens when a PWM frequency above the controller's maximum is requested.
+///
+/// * `REJECT` - Return an error (default)
+/// * `CLAMP` - Print a warning and use the maximum frequency instead
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Mode, PwmFrequencyLimit};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+/// gpio.set_pwm_frequency_limit(PwmFrequencyLimit::CLAMP);
+///
+/// // runs at the controller's maximum frequency instead of failing
+/// let mut pwm = gpio.pwm(33, 10_000_000.0).unwrap();
+/// pwm.start(50.0).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Copy)]
+pub enum PwmFrequencyLimit {
+    REJECT,
+    CLAMP,
+}
+
+/// Specifies the polarity of a hardware PWM output.
+///
+/// * `NORMAL` - The output is HIGH for the duty cycle and LOW for the rest of the period (default)
+/// * `INVERSED` - The output is LOW for the duty cycle and HIGH for the rest of the period
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Polarity {
+    NORMAL,
+    INVERSED,
+}
+
+fn pwm_path(ch_info: &ChannelInfo) -> String {
+    format!(
+        "{}/pwm{}",
+        ch_info.pwm_chip_dir.as_ref().unwrap(),
+        ch_info.pwm_id.unwrap()
+    )
+}
+
+fn write_pwm_file(path: String, value: String) -> Result<(), Error> {
+    let mut f = fs::OpenOptions::new().write(true).open(&path)?;
+    f.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+fn read_pwm_file(path: String) -> Result<u64, Error> {
+    let value = fs::read_to_string(&path).map_err(|e| Error::new(e).context(format!("Failed to read {}", path)))?;
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| Error::msg(format!("Unexpected value in {}: '{}'", path, value.trim())))
+}
+
+pub(crate) fn export_pwm(ch_info: &ChannelInfo, timeout: Duration) -> Result<(), Error> {
+    if !Path::new(&pwm_path(ch_info)).exists() {
+        let export_path = format!("{}/export", ch_info.pwm_chip_dir.as_ref().unwrap());
+        write_pwm_file(export_path, ch_info.pwm_id.unwrap().to_string())
+            .map_err(|e| e.context(format!("Failed to export PWM of channel {}", ch_info.channel)))?;
+    }
+
+    let enable_path = format!("{}/enable", pwm_path(ch_info));
+    let deadline = Instant::now() + timeout;
+    while fs::OpenOptions::new().read(true).write(true).open(&enable_path).is_err() {
+        if Instant::now() >= deadline {
+            return Err(Error::msg(format!(
+                "Timed out waiting for {} to become writable (channel {})",
+                enable_path, ch_info.channel
+            )));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn unexport_pwm(ch_info: &ChannelInfo) -> Result<(), Error> {
+    if Path::new(&pwm_path(ch_info)).exists() {
+        let unexport_path = format!("{}/unexport", ch_info.pwm_chip_dir.as_ref().unwrap());
+        write_pwm_file(unexport_path, ch_info.pwm_id.unwrap().to_string())?;
+    }
+
+    Ok(())
+}
+
+fn set_pwm_period(ch_info: &ChannelInfo, period_ns: u64) -> Result<(), Error> {
+    write_pwm_file(format!("{}/period", pwm_path(ch_info)), period_ns.to_string())
+}
+
+fn set_pwm_duty_cycle(ch_info: &ChannelInfo, duty_cycle_ns: u64) -> Result<(), Error> {
+    // On boot, both period and duty cycle are 0, and any change made while the
+    // period is 0 is rejected. A duty cycle of 0 is already in place then, so
+    // the write can be skipped.
+    if duty_cycle_ns == 0 {
+        let period = fs::read_to_string(format!("{}/period", pwm_path(ch_info)))?;
+        if period.trim() == "0" {
+            return Ok(());
+        }
+    }
+
+    write_pwm_file(format!("{}/duty_cycle", pwm_path(ch_info)), duty_cycle_ns.to_string())
+}
+
+fn enable_pwm(ch_info: &ChannelInfo) -> Result<(), Error> {
+    write_pwm_file(format!("{}/enable", pwm_path(ch_info)), String::from("1"))
+}
+
+pub(crate) fn disable_pwm(ch_info: &ChannelInfo) -> Result<(), Error> {
+    let enable_path = format!("{}/enable", pwm_path(ch_info));
+    if Path::new(&enable_path).exists() {
+        write_pwm_file(enable_path, String::from("0"))?;
+    }
+
+    Ok(())
+}
+
+/// A hardware PWM output, created with `GPIO::pwm`.
+///
+/// The PWM is configured but not running until `start` is called. The PWM
+/// channel is unexported when the channel is cleaned up with `GPIO::cleanup`.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Mode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// let mut pwm = gpio.pwm(33, 50.0).unwrap();
+/// pwm.start(25.0).unwrap();
+/// pwm.change_duty_cycle(75.0).unwrap();
+/// pwm.change_frequency(100.0).unwrap();
+/// pwm.stop().unwrap();
+///
+/// gpio.cleanup(Some(vec![33])).unwrap();
+/// ```
+pub struct PWM {
+    ch_info: ChannelInfo,
+    started: bool,
+    frequency_hz: f64,
+    period_ns: u64,
+    duty_cycle_percent: f64,
+
+    max_frequency_hz: Option<f64>,
+    frequency_limit: PwmFrequencyLimit,
+    warning_handler: Option<WarningHandler>,
+}
+
+impl PWM {
+    pub(crate) fn new(
+        ch_info: ChannelInfo,
+        frequency_hz: f64,
+        max_frequency_hz: Option<f64>,
+        frequency_limit: PwmFrequencyLimit,
+        warning_handler: Option<WarningHandler>,
+        export_timeout: Duration,
+    ) -> Result<PWM, Error> {
+        let mut pwm = PWM {
+            ch_info,
+            started: false,
+            frequency_hz: 0.0,
+            period_ns: 0,
+            duty_cycle_percent: 0.0,
+
+            max_frequency_hz,
+            frequency_limit,
+            warning_handler,
+        };
+
+        // check the frequency before touching sysfs
+        let frequency_hz = pwm.check_frequency(frequency_hz)?;
+        // anything that doesn't match the requested frequency
+        pwm.frequency_hz = -frequency_hz;
+
+        export_pwm(&pwm.ch_info, export_timeout)?;
+        set_pwm_duty_cycle(&pwm.ch_info, 0)?;
+        pwm.reconfigure(frequency_hz, 0.0, false)?;
+
+        Ok(pwm)
+    }
+
+    /// Starts the PWM output with the given duty cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `duty_cycle_percent` - The duty cycle, between 0.0 and 100.0
+    pub fn start(&mut self, duty_cycle_percent: f64) -> Result<(), Error> {
+        self.reconfigure(self.frequency_hz, duty_cycle_percent, true)
+    }
+
+    /// Stops the PWM output. It can be started again with `start`.
+    pub fn stop(&mut self) -> Result<(), Error> {
+        if !self.started {
+            return Ok(());
+        }
+
+        self.started = false;
+        disable_pwm(&self.ch_info)
+    }
+
+    /// Changes the frequency of the PWM output, keeping the duty cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency_hz` - The new frequency in Hz
+    pub fn change_frequency(&mut self, frequency_hz: f64) -> Result<(), Error> {
+        self.reconfigure(frequency_hz, self.duty_cycle_percent, false)
+    }
+
+    /// Changes the duty cycle of the PWM output, keeping the frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `duty_cycle_percent` - The new duty cycle, between 0.0 and 100.0
+    pub fn change_duty_cycle(&mut self, duty_cycle_percent: f64) -> Result<(), Error> {
+        self.reconfigure(self.frequency_hz, duty_cycle_percent, false)
+    }
+
+    /// Returns the duty cycle in percent, as currently configured in sysfs.
+    ///
+    /// Unlike the value passed to `start` or `change_duty_cycle`, this is
+    /// computed from the `duty_cycle` and `period` files of the PWM channel, so
+    /// it reflects rounding to whole nanoseconds and changes made by other processes.
+    pub fn duty_cycle(&self) -> Result<f64, Error> {
+        let period_ns = read_pwm_file(format!("{}/period", pwm_path(&self.ch_info)))?;
+        if period_ns == 0 {
+            return Ok(0.0);
+        }
+
+        let duty_cycle_ns = read_pwm_file(format!("{}/duty_cycle", pwm_path(&self.ch_info)))?;
+        Ok(duty_cycle_ns as f64 / period_ns as f64 * 100.0)
+    }
+
+    /// Returns the frequency in Hz, as currently configured in sysfs.
+    ///
+    /// The frequency is computed from the `period` file of the PWM channel, so
+    /// it can differ slightly from the requested one. It is 0.0 if no period has
+    /// been set.
+    pub fn frequency(&self) -> Result<f64, Error> {
+        let period_ns = read_pwm_file(format!("{}/period", pwm_path(&self.ch_info)))?;
+        if period_ns == 0 {
+            return Ok(0.0);
+        }
+
+        Ok(1_000_000_000.0 / period_ns as f64)
+    }
+
+    /// Returns `true` if the PWM output is enabled in sysfs.
+    pub fn is_running(&self) -> Result<bool, Error> {
+        Ok(read_pwm_file(format!("{}/enable", pwm_path(&self.ch_info)))? == 1)
+    }
+
+    /// Sets the polarity of the PWM output.
+    ///
+    /// The kernel only accepts a new polarity while the output is disabled, so
+    /// a running output is stopped, changed and started again. Returns an error
+    /// if the PWM controller has no polarity setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `polarity` - `Polarity::NORMAL` or `Polarity::INVERSED`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use jetson_gpio::{GPIO, Mode, Polarity};
+    ///
+    /// let mut gpio = GPIO::new();
+    /// gpio.setmode(Mode::BOARD).unwrap();
+    ///
+    /// let mut pwm = gpio.pwm(33, 50.0).unwrap();
+    /// pwm.set_polarity(Polarity::INVERSED).unwrap();
+    /// pwm.start(25.0).unwrap();
+    /// ```
+    pub fn set_polarity(&mut self, polarity: Polarity) -> Result<(), Error> {
+        let polarity_path = format!("{}/polarity", pwm_path(&self.ch_info));
+        if !Path::new(&polarity_path).exists() {
+            return Err(Error::msg(format!(
+                "The PWM controller of channel {} does not support setting the polarity",
+                self.ch_info.channel
+            )));
+        }
+
+        if self.started {
+            disable_pwm(&self.ch_info)?;
+        }
+
+        let value = match polarity {
+            Polarity::NORMAL => "normal",
+            Polarity::INVERSED => "inversed",
+        };
+        let result = write_pwm_file(polarity_path, String::from(value));
+
+        // restart the output even if the polarity was rejected
+        if self.started {
+            enable_pwm(&self.ch_info)?;
+        }
+
+        result
+    }
+
+    fn check_frequency(&self, frequency_hz: f64) -> Result<f64, Error> {
+        if !frequency_hz.is_finite() || frequency_hz <= 0.0 {
+            return Err(Error::msg(format!("Invalid PWM frequency: {} Hz", frequency_hz)));
+        }
+
+        match self.max_frequency_hz {
+            Some(max_frequency_hz) if frequency_hz > max_frequency_hz => match self.frequency_limit {
+                PwmFrequencyLimit::REJECT => Err(Error::msg(format!(
+                    "PWM frequency {} Hz exceeds the maximum of {} Hz supported by channel {}",
+                    frequency_hz, max_frequency_hz, self.ch_info.channel
+                ))),
+                PwmFrequencyLimit::CLAMP => {
+                    if let Some(warning_handler) = &self.warning_handler {
+                        warning_handler(&format!(
+                            "PWM frequency {} Hz exceeds the maximum of {} Hz supported by channel {}, clamping",
+                            frequency_hz, max_frequency_hz, self.ch_info.channel
+                        ));
+                    }
+                    Ok(max_frequency_hz)
+                }
+            },
+            _ => Ok(frequency_hz),
+        }
+    }
+
+    fn reconfigure(&mut self, frequency_hz: f64, duty_cycle_percent: f64, start: bool) -> Result<(), Error> {
+        if !(0.0..=100.0).contains(&duty_cycle_percent) {
+            return Err(Error::msg("The duty cycle must be between 0.0 and 100.0"));
+        }
+        let frequency_hz = self.check_frequency(frequency_hz)?;
+
+        let freq_change = start || frequency_hz != self.frequency_hz;
+        let stop = self.started && freq_change;
+        if stop {
+            self.started = false;
+            disable_pwm(&self.ch_info)?;
+        }
+
+        if freq_change {
+            self.frequency_hz = frequency_hz;
+            self.period_ns = (1_000_000_000.0 / frequency_hz) as u64;
+            // reset the duty cycle in case the previous one is longer than the new period
+            set_pwm_duty_cycle(&self.ch_info, 0)?;
+            set_pwm_period(&self.ch_info, self.period_ns)?;
+        }
+
+        self.duty_cycle_percent = duty_cycle_percent;
+        let duty_cycle_ns = (self.period_ns as f64 * (duty_cycle_percent / 100.0)) as u64;
+        set_pwm_duty_cycle(&self.ch_info, duty_cycle_ns)?;
+
+        if stop || start {
+            enable_pwm(&self.ch_info)?;
+            self.started = true;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpio_pin_data::Channel;
+
+    fn test_pwm(frequency_limit: PwmFrequencyLimit) -> PWM {
+        test_pwm_in("/sys/devices/7000a000.pwm/pwm/pwmchip0", frequency_limit)
+    }
+
+    fn test_pwm_in(pwm_chip_dir: &str, frequency_limit: PwmFrequencyLimit) -> PWM {
+        PWM {
+            ch_info: ChannelInfo {
+                channel: Channel::Number(33),
+                gpio_chip_dir: String::from("/sys/devices/platform/6000d000.gpio"),
+                gpio_chip_dev: Some(String::from("/dev/gpiochip0")),
+                line_offset: 38,
+                global_gpio: 38,
+                global_gpio_name: String::from("gpio38"),
+                pwm_chip_dir: Some(String::from(pwm_chip_dir)),
+                pwm_id: Some(2),
+            },
+            started: false,
+            frequency_hz: 50.0,
+            period_ns: 20_000_000,
+            duty_cycle_percent: 0.0,
+            max_frequency_hz: Some(187_500.0),
+            frequency_limit,
+            warning_handler: None,
+        }
+    }
+
+    #[test]
+    fn frequency_above_maximum_is_rejected_or_clamped() {
+        let pwm = test_pwm(PwmFrequencyLimit::REJECT);
+        assert_eq!(pwm.check_frequency(1000.0).unwrap(), 1000.0);
+        assert!(pwm.check_frequency(1_000_000.0).is_err());
+        assert!(pwm.check_frequency(0.0).is_err());
+
+        let pwm = test_pwm(PwmFrequencyLimit::CLAMP);
+        assert_eq!(pwm.check_frequency(1_000_000.0).unwrap(), 187_500.0);
+    }
+
+    #[test]
+    fn configuration_is_read_back_from_sysfs() {
+        let pwm_chip_dir = std::env::temp_dir().join(format!("jetson-gpio-pwm-{}", std::process::id()));
+        let pwm_dir = pwm_chip_dir.join("pwm2");
+        fs::create_dir_all(&pwm_dir).unwrap();
+        fs::write(pwm_dir.join("period"), "20000000\n").unwrap();
+        fs::write(pwm_dir.join("duty_cycle"), "5000000\n").unwrap();
+        fs::write(pwm_dir.join("enable"), "1\n").unwrap();
+
+        let pwm = test_pwm_in(pwm_chip_dir.to_str().unwrap(), PwmFrequencyLimit::REJECT);
+        assert_eq!(pwm.frequency().unwrap(), 50.0);
+        assert_eq!(pwm.duty_cycle().unwrap(), 25.0);
+        assert!(pwm.is_running().unwrap());
+
+        fs::write(pwm_dir.join("enable"), "0\n").unwrap();
+        assert!(!pwm.is_running().unwrap());
+
+        fs::remove_dir_all(&pwm_chip_dir).unwrap();
+        assert!(pwm.frequency().is_err());
+    }
+
+    #[test]
+    fn polarity_is_set_while_disabled() {
+        let pwm_chip_dir = std::env::temp_dir().join(format!("jetson-gpio-polarity-{}", std::process::id()));
+        let pwm_dir = pwm_chip_dir.join("pwm2");
+        fs::create_dir_all(&pwm_dir).unwrap();
+        fs::write(pwm_dir.join("enable"), "1").unwrap();
+
+        let mut pwm = test_pwm_in(pwm_chip_dir.to_str().unwrap(), PwmFrequencyLimit::REJECT);
+        pwm.started = true;
+        assert!(pwm.set_polarity(Polarity::INVERSED).is_err());
+
+        fs::write(pwm_dir.join("polarity"), "normal").unwrap();
+        pwm.set_polarity(Polarity::INVERSED).unwrap();
+        assert_eq!(fs::read_to_string(pwm_dir.join("polarity")).unwrap(), "inversed");
+        assert_eq!(fs::read_to_string(pwm_dir.join("enable")).unwrap(), "1");
+
+        fs::remove_dir_all(&pwm_chip_dir).unwrap();
+    }
+
+    #[test]
+    fn export_times_out_if_enable_never_appears() {
+        let pwm_chip_dir = std::env::temp_dir().join(format!("jetson-gpio-export-{}", std::process::id()));
+        fs::create_dir_all(pwm_chip_dir.join("pwm2")).unwrap();
+
+        let pwm = test_pwm_in(pwm_chip_dir.to_str().unwrap(), PwmFrequencyLimit::REJECT);
+        let err = export_pwm(&pwm.ch_info, Duration::from_millis(50)).unwrap_err();
+        assert!(err.to_string().contains("pwm2/enable"));
+
+        fs::remove_dir_all(&pwm_chip_dir).unwrap();
+    }
+}
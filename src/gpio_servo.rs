@@ -0,0 +1,168 @@
+use anyhow::Error;
+use std::time::Duration;
+
+use crate::gpio_pwm::PWM;
+
+/// The pulse widths and frequency of a hobby servo, used by `GPIO::servo`.
+///
+/// The default matches most hobby servos: a 50 Hz signal whose pulse is 1 ms
+/// wide at 0 degrees and 2 ms wide at 180 degrees.
+///
+/// * `min_pulse` - The pulse width at 0 degrees
+/// * `max_pulse` - The pulse width at `max_angle`
+/// * `frequency_hz` - The frequency of the PWM signal
+/// * `max_angle` - The angle reached with `max_pulse`, in degrees
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use jetson_gpio::ServoConfig;
+///
+/// // a servo that needs 0.5 to 2.5 ms pulses for its full range
+/// let config = ServoConfig {
+///     min_pulse: Duration::from_micros(500),
+///     max_pulse: Duration::from_micros(2500),
+///     ..ServoConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ServoConfig {
+    pub min_pulse: Duration,
+    pub max_pulse: Duration,
+    pub frequency_hz: f64,
+    pub max_angle: f64,
+}
+
+impl Default for ServoConfig {
+    fn default() -> Self {
+        ServoConfig {
+            min_pulse: Duration::from_millis(1),
+            max_pulse: Duration::from_millis(2),
+            frequency_hz: 50.0,
+            max_angle: 180.0,
+        }
+    }
+}
+
+impl ServoConfig {
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if self.min_pulse >= self.max_pulse {
+            return Err(Error::msg("The minimum servo pulse must be shorter than the maximum pulse"));
+        }
+        if self.max_angle <= 0.0 || self.max_angle.is_nan() {
+            return Err(Error::msg("The maximum servo angle must be positive"));
+        }
+        if self.max_pulse.as_secs_f64() * self.frequency_hz > 1.0 {
+            return Err(Error::msg(format!(
+                "The maximum servo pulse of {:?} does not fit in the period of {} Hz",
+                self.max_pulse, self.frequency_hz
+            )));
+        }
+
+        Ok(())
+    }
+
+    // The duty cycle in percent that positions the servo at `degrees`.
+    fn duty_cycle(&self, degrees: f64) -> Result<f64, Error> {
+        if !(0.0..=self.max_angle).contains(&degrees) {
+            return Err(Error::msg(format!(
+                "The servo angle must be between 0 and {} degrees",
+                self.max_angle
+            )));
+        }
+
+        let min_pulse = self.min_pulse.as_secs_f64();
+        let pulse = min_pulse + (self.max_pulse.as_secs_f64() - min_pulse) * degrees / self.max_angle;
+        Ok(pulse * self.frequency_hz * 100.0)
+    }
+}
+
+/// A hobby servo on a hardware PWM channel, created with `GPIO::servo`.
+///
+/// The servo is not driven until the first call of `set_angle`. `stop`
+/// stops the signal, which lets most servos move freely.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, Mode, ServoConfig};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// let mut servo = gpio.servo(33, ServoConfig::default()).unwrap();
+/// servo.set_angle(90.0).unwrap();
+/// servo.stop().unwrap();
+/// ```
+pub struct Servo {
+    pwm: PWM,
+    config: ServoConfig,
+    angle: Option<f64>,
+}
+
+impl Servo {
+    pub(crate) fn new(pwm: PWM, config: ServoConfig) -> Servo {
+        Servo {
+            pwm,
+            config,
+            angle: None,
+        }
+    }
+
+    /// Returns the configuration of the servo.
+    pub fn config(&self) -> &ServoConfig {
+        &self.config
+    }
+
+    /// Returns the angle last set with `set_angle`, or `None` if the servo is not driven.
+    pub fn angle(&self) -> Option<f64> {
+        self.angle
+    }
+
+    /// Moves the servo to the given angle.
+    ///
+    /// # Arguments
+    ///
+    /// * `degrees` - The angle, between 0.0 and the `max_angle` of the configuration
+    pub fn set_angle(&mut self, degrees: f64) -> Result<(), Error> {
+        let duty_cycle = self.config.duty_cycle(degrees)?;
+        match self.angle {
+            Some(_) => self.pwm.change_duty_cycle(duty_cycle)?,
+            None => self.pwm.start(duty_cycle)?,
+        }
+        self.angle = Some(degrees);
+
+        Ok(())
+    }
+
+    /// Stops driving the servo. The next `set_angle` drives it again.
+    pub fn stop(&mut self) -> Result<(), Error> {
+        self.pwm.stop()?;
+        self.angle = None;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn angles_map_to_pulse_widths() {
+        let config = ServoConfig::default();
+        assert!(config.check().is_ok());
+        assert!((config.duty_cycle(0.0).unwrap() - 5.0).abs() < 1e-9);
+        assert!((config.duty_cycle(90.0).unwrap() - 7.5).abs() < 1e-9);
+        assert!((config.duty_cycle(180.0).unwrap() - 10.0).abs() < 1e-9);
+        assert!(config.duty_cycle(181.0).is_err());
+        assert!(config.duty_cycle(-1.0).is_err());
+
+        let config = ServoConfig {
+            frequency_hz: 1000.0,
+            ..ServoConfig::default()
+        };
+        assert!(config.check().is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::gpio::GPIO;
+
+/// A cloneable handle to a `GPIO` object that can be sent to other threads.
+///
+/// Created with `GPIO::shared`. Every clone refers to the same `GPIO` object,
+/// which is guarded by a read-write lock:
+///
+/// * `read` gives shared access to the methods that take `&self`, such as
+///   `input`, `output`, `event_detected` and `kick_watchdog`. Any number of
+///   threads can hold it at the same time. Each channel has its own value file
+///   or requested line, so reading and writing different channels concurrently
+///   is safe.
+/// * `write` gives exclusive access to the methods that take `&mut self`, such
+///   as `setmode`, `setup`, `cleanup` and `add_event_detect`. These export and
+///   unexport channels and change the channel configuration, so they wait until
+///   no other thread holds the lock.
+///
+/// A thread that panics while holding the lock does not make the handle
+/// unusable; the other threads keep access to the `GPIO` object.
+///
+/// # Example
+///
+/// ```rust
+/// use std::thread;
+/// use jetson_gpio::{GPIO, Direction, Level, Mode};
+///
+/// let gpio = GPIO::new().shared();
+/// gpio.write().setmode(Mode::BOARD).unwrap();
+/// gpio.write().setup(vec![7], Direction::OUT, None, None).unwrap();
+///
+/// let handle = gpio.clone();
+/// thread::spawn(move || {
+///     handle.read().output(vec![7], vec![Level::HIGH]).unwrap();
+/// })
+/// .join()
+/// .unwrap();
+///
+/// gpio.write().cleanup_all().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct SharedGpio {
+    inner: Arc<RwLock<GPIO>>,
+}
+
+impl SharedGpio {
+    pub(crate) fn new(gpio: GPIO) -> SharedGpio {
+        SharedGpio {
+            inner: Arc::new(RwLock::new(gpio)),
+        }
+    }
+
+    /// Locks the `GPIO` object for shared access, blocking while another thread holds `write`.
+    pub fn read(&self) -> RwLockReadGuard<'_, GPIO> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the `GPIO` object for exclusive access, blocking while any other thread holds the lock.
+    pub fn write(&self) -> RwLockWriteGuard<'_, GPIO> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::Error;
+
+use crate::gpio::Level;
+use crate::gpio_pin::{InputPin, OutputPin};
+
+/// Specifies the clock polarity and phase of a software SPI bus.
+///
+/// * `MODE0` - The clock idles LOW, data is sampled on the rising edge
+/// * `MODE1` - The clock idles LOW, data is sampled on the falling edge
+/// * `MODE2` - The clock idles HIGH, data is sampled on the falling edge
+/// * `MODE3` - The clock idles HIGH, data is sampled on the rising edge
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpiMode {
+    MODE0,
+    MODE1,
+    MODE2,
+    MODE3,
+}
+
+impl SpiMode {
+    // the level of the clock between transfers
+    pub(crate) fn idle(self) -> Level {
+        match self {
+            SpiMode::MODE0 | SpiMode::MODE1 => Level::LOW,
+            SpiMode::MODE2 | SpiMode::MODE3 => Level::HIGH,
+        }
+    }
+
+    // whether data is sampled on the second edge of each clock cycle
+    fn second_edge(self) -> bool {
+        matches!(self, SpiMode::MODE1 | SpiMode::MODE3)
+    }
+}
+
+/// Specifies the order in which the bits of a byte are shifted out and in.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    MSB_FIRST,
+    LSB_FIRST,
+}
+
+/// A software (bit-banged) SPI bus on four header pins, created with `GPIO::setup_spi`.
+///
+/// The bus drives SCK, MOSI and CS through output pin handles and samples MISO
+/// through an input pin handle, so each transfer skips the checks done by
+/// `GPIO::output` and `GPIO::input`. The clock runs as fast as the backend can
+/// toggle the pins: every bit takes four pin accesses, which with the sysfs
+/// backend limits SCK to roughly 10 to 50 kHz. The character device backend
+/// accesses a pin with a single ioctl and reaches a few hundred kHz. In both
+/// cases the clock is irregular, so the peripheral must tolerate a jittery SCK.
+///
+/// # Example
+///
+/// ```rust
+/// use jetson_gpio::{GPIO, BitOrder, Mode, SpiMode};
+///
+/// let mut gpio = GPIO::new();
+/// gpio.setmode(Mode::BOARD).unwrap();
+///
+/// let spi = gpio.setup_spi(23, 19, 21, 24, SpiMode::MODE0, BitOrder::MSB_FIRST).unwrap();
+/// let response = spi.transfer(&[0x9f, 0x00, 0x00]).unwrap();
+/// ```
+pub struct BitBangSpi {
+    sck: OutputPin,
+    mosi: OutputPin,
+    miso: InputPin,
+    cs: OutputPin,
+    mode: SpiMode,
+    bit_order: BitOrder,
+}
+
+impl BitBangSpi {
+    pub(crate) fn new(
+        sck: OutputPin,
+        mosi: OutputPin,
+        miso: InputPin,
+        cs: OutputPin,
+        mode: SpiMode,
+        bit_order: BitOrder,
+    ) -> BitBangSpi {
+        BitBangSpi {
+            sck,
+            mosi,
+            miso,
+            cs,
+            mode,
+            bit_order,
+        }
+    }
+
+    /// Returns the clock mode of the bus.
+    pub fn mode(&self) -> SpiMode {
+        self.mode
+    }
+
+    /// Sets the clock mode used by the following transfers.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The clock polarity and phase expected by the peripheral
+    pub fn set_mode(&mut self, mode: SpiMode) -> Result<(), Error> {
+        self.mode = mode;
+        self.sck.set(mode.idle())
+    }
+
+    /// Returns the bit order of the bus.
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// Sets the bit order used by the following transfers.
+    ///
+    /// # Arguments
+    ///
+    /// * `bit_order` - `BitOrder::MSB_FIRST` or `BitOrder::LSB_FIRST`
+    pub fn set_bit_order(&mut self, bit_order: BitOrder) {
+        self.bit_order = bit_order;
+    }
+
+    /// Writes `data` to the peripheral and returns the bytes read back at the same time.
+    ///
+    /// CS is held LOW for the whole transfer and released afterwards, also if
+    /// accessing a pin fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to send, the returned bytes have the same length
+    pub fn transfer(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        self.cs.set_low()?;
+        let received = data.iter().map(|byte| self.transfer_byte(*byte)).collect();
+        self.cs.set_high()?;
+
+        received
+    }
+
+    fn transfer_byte(&self, byte: u8) -> Result<u8, Error> {
+        let idle = self.mode.idle();
+        let active = idle.inverted();
+
+        let mut received = 0u8;
+        for i in 0..8 {
+            let bit = match self.bit_order {
+                BitOrder::MSB_FIRST => 7 - i,
+                BitOrder::LSB_FIRST => i,
+            };
+            let out = match byte & (1 << bit) {
+                0 => Level::LOW,
+                _ => Level::HIGH,
+            };
+
+            // the data is shifted out on the edge before the one it is sampled on
+            let level = match self.mode.second_edge() {
+                false => {
+                    self.mosi.set(out)?;
+                    self.sck.set(active.clone())?;
+                    let level = self.miso.read()?;
+                    self.sck.set(idle.clone())?;
+                    level
+                }
+                true => {
+                    self.sck.set(active.clone())?;
+                    self.mosi.set(out)?;
+                    self.sck.set(idle.clone())?;
+                    self.miso.read()?
+                }
+            };
+
+            if level == Level::HIGH {
+                received |= 1 << bit;
+            }
+        }
+
+        Ok(received)
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::gpio::{output_one, Level, WarningHandler};
+use crate::gpio_cdev::set_line_value;
+use crate::gpio_pin_data::{Channel, ChannelInfo};
+
+// An output the watchdog drives when it trips, with the requested line and line
+// index of the character device backend, if any. `level` is the electrical level
+// to drive, `logical_level` the same level as the program sees it.
+pub(crate) struct SafeState {
+    pub(crate) ch_info: ChannelInfo,
+    pub(crate) line: Option<(File, u32)>,
+    pub(crate) level: Level,
+    pub(crate) logical_level: Level,
+}
+
+enum Message {
+    Kick,
+    Stop,
+}
+
+/// A background thread that drives outputs to their safe levels when it is not
+/// kicked within the timeout.
+///
+/// The watchdog trips once per missed deadline: after driving the safe levels
+/// it stays idle until it is kicked again, which re-arms it. A channel that
+/// cannot be driven is reported to the warning handler, and the others are
+/// still driven. Dropping the watchdog stops the thread.
+///
+/// Each safe state carries the requested line of the character device
+/// backend with the index of the channel's line in it, or `None` to write through sysfs.
+pub(crate) struct Watchdog {
+    channels: Vec<Channel>,
+    sender: Sender<Message>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    pub(crate) fn start(
+        timeout: Duration,
+        safe_states: Vec<SafeState>,
+        last_outputs: Arc<Mutex<HashMap<Channel, Level>>>,
+        warning_handler: Option<WarningHandler>,
+    ) -> Watchdog {
+        let channels = safe_states.iter().map(|state| state.ch_info.channel.clone()).collect();
+        let (sender, receiver) = mpsc::channel();
+
+        let thread = thread::spawn(move || {
+            let mut tripped = false;
+            loop {
+                match receiver.recv_timeout(timeout) {
+                    Ok(Message::Kick) => tripped = false,
+                    Ok(Message::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+                    Err(RecvTimeoutError::Timeout) => {
+                        if !tripped {
+                            // a failed channel must not keep the others from their safe levels
+                            for state in safe_states.iter() {
+                                let written = match &state.line {
+                                    Some((line, index)) => set_line_value(line, *index, state.level.clone()),
+                                    None => output_one(state.ch_info.clone(), state.level.clone()),
+                                };
+                                match written {
+                                    Ok(()) => {
+                                        let channel = state.ch_info.channel.clone();
+                                        last_outputs.lock().unwrap().insert(channel, state.logical_level.clone());
+                                    }
+                                    Err(e) => {
+                                        if let Some(warning_handler) = &warning_handler {
+                                            warning_handler(&format!(
+                                                "The watchdog failed to drive channel {} to its safe level: {}",
+                                                state.ch_info.channel, e
+                                            ));
+                                        }
+                                    }
+                                }
+                            }
+                            tripped = true;
+                        }
+                    }
+                }
+            }
+        });
+
+        Watchdog {
+            channels,
+            sender,
+            thread: Some(thread),
+        }
+    }
+
+    pub(crate) fn kick(&self) {
+        let _ = self.sender.send(Message::Kick);
+    }
+
+    pub(crate) fn watches(&self, channel: &Channel) -> bool {
+        self.channels.contains(channel)
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        let _ = self.sender.send(Message::Stop);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
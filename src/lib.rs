@@ -17,10 +17,10 @@
 //! let mut gpio = GPIO::new();
 //! gpio.setmode(Mode::BOARD).unwrap();
 //!
-//! gpio.setup(vec![7, 11], Direction::OUT, Some(Level::LOW)).unwrap();
+//! gpio.setup(vec![7, 11], Direction::OUT, None, Some(Level::LOW)).unwrap();
 //! gpio.output(vec![7, 11], vec![Level::HIGH, Level::HIGH]).unwrap();
 //!
-//! gpio.cleanup(None).unwrap();
+//! gpio.cleanup_all().unwrap();
 //! ```
 //!
 //! This example sets up two pins as outputs and sets them to an initial LOW value.
@@ -44,12 +44,128 @@
 //!
 //! * `Mode::BOARD` - The pin numbers are the physical pin numbers on the Jetson board.
 //! * `Mode::BCM` - The pin numbers are the Broadcom SOC channel numbers.
-//! * `Mode::TEGRA_SOC` - The pin numbers are the Tegra SOC channel numbers.
-//! * `Mode::CVM` - The pin numbers are the CVM channel numbers.
+//! * `Mode::TEGRA_SOC` - The pins are identified by their Tegra SOC names, e.g. `"GP66"`.
+//! * `Mode::CVM` - The pins are identified by their CVM signal names, e.g. `"GPIO27"`.
+//!
+//! The mode can also be set when the `GPIO` object is created, together with the
+//! warnings, backend and model, using `GPIO::builder`:
+//!
+//! ```rust
+//! use jetson_gpio::{GPIO, Mode};
+//!
+//! let gpio = GPIO::builder().mode(Mode::BOARD).warnings(false).build().unwrap();
+//! ```
 //!
 //! Using this library, you can configure GPIO pins as either inputs or outputs.
 //! You can also read the current value of an input pin or set the value of an
-//! output pin.
+//! output pin. Inputs can be watched for rising or falling edges with
+//! `add_event_detect` and `wait_for_edge`. PWM-capable pins can drive a hardware
+//! PWM output created with the `pwm` method. To use the same `GPIO` object from
+//! several threads, turn it into a cloneable `SharedGpio` handle with `shared`.
+//! Four pins can also act as a software SPI bus, created with `setup_spi`. For
+//! LEDs, `led` and `led_pwm` return an `Led` that can be switched and, on PWM
+//! channels, dimmed. Hobby servos on PWM channels are positioned by angle
+//! through a `Servo` created with `servo`.
+//!
+//! ## GPIO backends
+//!
+//! By default, the GPIO pins are controlled through the legacy sysfs interface
+//! (`/sys/class/gpio`). Newer kernels, such as the one in JetPack 6, ship without
+//! it. On those, use the GPIO character devices (`/dev/gpiochipN`) instead:
+//!
+//! ```rust
+//! use jetson_gpio::{GPIO, Backend, Direction, Mode};
+//!
+//! let mut gpio = GPIO::new_with_backend(Backend::CHARDEV);
+//! gpio.setmode(Mode::BOARD).unwrap();
+//! gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+//! ```
+//!
+//! The character device backend can also enable the internal pull-up or pull-down
+//! resistor of an input with `Pull::UP` or `Pull::DOWN`. The sysfs interface has no
+//! way to set it and prints a warning instead.
+//!
+//! To test code that uses this crate without a Jetson board, use the mock backend.
+//! It keeps the pins in memory, and inputs can be driven with `set_mock_input`:
+//!
+//! ```rust
+//! use jetson_gpio::{GPIO, Backend, Direction, Level, Mode};
+//!
+//! let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+//! gpio.setmode(Mode::BOARD).unwrap();
+//! gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+//! gpio.set_mock_input(12, Level::HIGH).unwrap();
+//! ```
+//!
+//! ## Containers
+//!
+//! Inside containers or on custom device trees, the board model may not be
+//! detectable. Set the `JETSON_MODEL_NAME` environment variable (e.g.
+//! `JETSON_MODEL_NAME=JETSON_NANO`) to use that model when detection fails, or
+//! force a model from code with `new_for_model`, which skips the detection
+//! entirely and returns an error listing the valid model names for an unknown one:
+//!
+//! ```rust
+//! use jetson_gpio::{GPIO, Mode};
+//!
+//! let mut gpio = GPIO::new_for_model("JETSON_ORIN_NX").unwrap();
+//! gpio.setmode(Mode::BOARD).unwrap();
+//! ```
+//!
+//! The container still needs access to the GPIO interface of the host, e.g. by
+//! mounting `/sys` for the sysfs backend or passing `/dev/gpiochipN` for the
+//! character device backend.
+//!
+//! If they are mounted elsewhere, set `JETSON_GPIO_SYSFS` (default `/sys`),
+//! `JETSON_GPIO_DEVICE_TREE` (default `/proc/device-tree`) or `JETSON_GPIO_DEV`
+//! (default `/dev`), or pass the locations to `new_with_paths` as `GpioPaths`.
+//!
+//! ## embedded-hal
+//!
+//! With the `embedded-hal` feature, the pin handles returned by `setup_output_pin`
+//! and `setup_input_pin` implement the `OutputPin`, `StatefulOutputPin` and
+//! `InputPin` traits of `embedded-hal` 1.0, so they can be passed to drivers
+//! written against it:
+//!
+//! ```toml
+//! [dependencies]
+//! jetson_gpio = { version = "0.1", features = ["embedded-hal"] }
+//! ```
+//!
+//! ## tokio
+//!
+//! With the `tokio` feature, `wait_for_edge_async` waits for an edge without
+//! blocking a thread, so a single tokio runtime can watch many inputs at once.
+//! `edge_stream` returns a `Stream` of the edges of an input, each with its time:
+//!
+//! ```toml
+//! [dependencies]
+//! jetson_gpio = { version = "0.1", features = ["tokio"] }
+//! ```
+//!
+//! ## serde
+//!
+//! With the `serde` feature, `Mode`, `Direction`, `Level`, `Pull`, `Channel`,
+//! `ChannelInfo`, `JetsonInfo` and the snapshots of `export_config` implement
+//! `Serialize` and `Deserialize`, e.g. to report the pin state of a board as JSON:
+//!
+//! ```toml
+//! [dependencies]
+//! jetson_gpio = { version = "0.1", features = ["serde"] }
+//! ```
+//!
+//! ## stub
+//!
+//! With the `stub` feature, every `GPIO` object uses the mock backend, whichever
+//! backend is requested. `GPIO::new()` and most of the API then work on a machine
+//! without GPIOs, e.g. to run the tests of an application in CI. Hardware PWM,
+//! edge detection and the watchdog are not simulated and return an error. Enable
+//! it for such builds only, as no pin of a Jetson is driven with it:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! jetson_gpio = { version = "0.1", features = ["stub"] }
+//! ```
 //!
 //! # Crate support
 //!
@@ -60,15 +176,21 @@
 //! This crate is **under development** and it currently only supports a subset of the
 //! functionality provided by the Python library. Currently supported boards:
 //!
-//! * Jetson Orin
+//! * Jetson AGX Orin
+//! * Jetson Orin NX
+//! * Jetson Orin Nano
 //! * Jetson Xavier NX
+//! * Jetson AGX Xavier
+//! * Jetson Nano
 //!
 //! Supported pin numbering modes:
 //!
 //! * `Mode::BOARD`
 //! * `Mode::BCM`
+//! * `Mode::TEGRA_SOC`
+//! * `Mode::CVM`
 //!
-//! Only GPIO pins without events are supported.
+//! GPIO inputs and outputs, edge events and hardware PWM are supported.
 //!
 //! # License
 //!
@@ -81,6 +203,31 @@
 //! if you have any questions or suggestions.
 
 mod gpio;
+mod gpio_builder;
+mod gpio_cdev;
+mod gpio_event;
+#[cfg(feature = "embedded-hal")]
+mod gpio_hal;
+mod gpio_led;
+mod gpio_mock;
+mod gpio_paths;
+mod gpio_pin;
 mod gpio_pin_data;
+mod gpio_pwm;
+mod gpio_servo;
+mod gpio_shared;
+mod gpio_spi;
+mod gpio_watchdog;
 pub use gpio::*;
+pub use gpio_builder::*;
+pub use gpio_event::*;
+#[cfg(feature = "embedded-hal")]
+pub use gpio_hal::*;
+pub use gpio_led::*;
+pub use gpio_paths::*;
+pub use gpio_pin::*;
 pub use gpio_pin_data::*;
+pub use gpio_pwm::*;
+pub use gpio_servo::*;
+pub use gpio_shared::*;
+pub use gpio_spi::*;
@@ -0,0 +1,535 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use jetson_gpio::{
+    Backend, BitOrder, Channel, Direction, Drive, Edge, GpioError, Level, Mode, Pull, ServoConfig, SpiMode, GPIO,
+};
+
+
+#[test]
+fn test_mock_outputs_and_inputs() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7, 11], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+    gpio.setup(vec![13], Direction::IN, Pull::UP, None).unwrap();
+
+    assert!(gpio.input_many(vec![7, 11, 12, 13]).unwrap() == vec![Level::LOW, Level::LOW, Level::LOW, Level::HIGH]);
+
+    gpio.output(vec![7, 11], vec![Level::HIGH, Level::LOW]).unwrap();
+    assert!(gpio.input(7).unwrap() == Level::HIGH);
+    assert!(gpio.input(11).unwrap() == Level::LOW);
+
+    gpio.set_mock_input(12, Level::HIGH).unwrap();
+    assert!(gpio.input(12).unwrap() == Level::HIGH);
+    assert!(gpio.set_mock_input(7, Level::LOW).is_err());
+    assert!(gpio.output(vec![12], vec![Level::LOW]).is_err());
+
+    gpio.cleanup(Some(vec![7])).unwrap();
+    assert!(gpio.input(7).is_err());
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_setup_with_initials() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    gpio.setup_with_initials(vec![7, 11], Direction::OUT, vec![Level::HIGH, Level::LOW]).unwrap();
+    assert!(gpio.input_many(vec![7, 11]).unwrap() == vec![Level::HIGH, Level::LOW]);
+
+    let err = gpio.setup_with_initials(vec![7, 11], Direction::OUT, vec![Level::HIGH]).err().unwrap();
+    assert_eq!(err.to_string(), "1 initial values provided for 2 channels");
+    assert!(gpio.setup_with_initials(vec![12], Direction::IN, vec![Level::HIGH]).is_err());
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_channels_from_arrays_and_iterators() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    gpio.setup([7, 11], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    gpio.output([7, 11], vec![Level::HIGH]).unwrap();
+    assert_eq!(gpio.input_many((7..=11).step_by(4)).unwrap(), vec![Level::HIGH, Level::HIGH]);
+    gpio.toggle([11]).unwrap();
+    assert_eq!(gpio.input_many([7, 11]).unwrap(), vec![Level::HIGH, Level::LOW]);
+
+    gpio.cleanup(Some([7, 11])).unwrap();
+    assert_eq!(gpio.mode(), None);
+}
+
+#[test]
+fn test_mock_ensure_setup() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    assert_eq!(gpio.ensure_setup(7, Direction::OUT).unwrap(), Direction::OUT);
+    // the output was left alone, so it still drives its level
+    assert_eq!(gpio.input(7).unwrap(), Level::HIGH);
+
+    assert_eq!(gpio.ensure_setup(7, Direction::IN).unwrap(), Direction::IN);
+    assert!(gpio.output(vec![7], vec![Level::LOW]).is_err());
+    assert_eq!(gpio.ensure_setup(11, Direction::OUT).unwrap(), Direction::OUT);
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_open_drain() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    // released until driven, the floating line reads HIGH
+    gpio.setup_output_with_drive(vec![7, 11], Drive::OPEN_DRAIN, None).unwrap();
+    assert_eq!(gpio.input_many(vec![7, 11]).unwrap(), vec![Level::HIGH, Level::HIGH]);
+
+    gpio.output(vec![7], vec![Level::LOW]).unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::LOW);
+    gpio.output_atomic(vec![7, 11], vec![Level::HIGH, Level::LOW]).unwrap();
+    assert_eq!(gpio.input_many(vec![7, 11]).unwrap(), vec![Level::HIGH, Level::LOW]);
+    assert_eq!(gpio.last_output(7), Some(Level::HIGH));
+
+    // setup makes the channel push-pull again
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    gpio.output(vec![7], vec![Level::LOW]).unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::LOW);
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_wait_for_level() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+
+    gpio.set_mock_input(12, Level::HIGH).unwrap();
+    assert!(gpio.wait_for_level(12, Level::HIGH, None).unwrap());
+
+    let start = Instant::now();
+    assert!(!gpio.wait_for_level(12, Level::LOW, Some(Duration::from_millis(20))).unwrap());
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    assert!(gpio.wait_for_level(7, Level::LOW, None).is_err());
+}
+
+#[test]
+fn test_mock_tristate() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+
+    gpio.set_tristate(7).unwrap();
+    gpio.set_tristate(7).unwrap();
+    assert_eq!(gpio.dump_state().unwrap()[0].1, Direction::TRISTATE);
+    assert!(gpio.output(vec![7], vec![Level::LOW]).is_err());
+
+    // the line is read while another device drives it
+    gpio.set_mock_input(7, Level::LOW).unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::LOW);
+
+    gpio.release_tristate(7).unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::HIGH);
+    gpio.output(vec![7], vec![Level::LOW]).unwrap();
+
+    let err = gpio.release_tristate(7).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<GpioError>(),
+        Some(&GpioError::WRONG_DIRECTION(Channel::Number(7), Direction::TRISTATE))
+    );
+    gpio.setup(vec![11], Direction::IN, None, None).unwrap();
+    assert!(gpio.set_tristate(11).is_err());
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_available_channels() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    assert!(gpio.available_channels().is_err());
+    assert!(gpio.channel_info(7).is_none());
+
+    gpio.setmode(Mode::BOARD).unwrap();
+    let channels = gpio.available_channels().unwrap();
+    assert!(channels.contains(&Channel::from(7)));
+    assert!(channels.windows(2).all(|w| w[0] < w[1]));
+    assert!(gpio.channel_info(7).is_some());
+    assert!(gpio.channel_info(1).is_none());
+}
+
+#[test]
+fn test_mock_pin_handles() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    let led = gpio.setup_output_pin(7, Some(Level::LOW)).unwrap();
+    led.set_high().unwrap();
+    assert!(gpio.input(7).unwrap() == Level::HIGH);
+    led.toggle().unwrap();
+    assert!(led.read().unwrap() == Level::LOW);
+
+    let button = gpio.setup_input_pin(12, Pull::UP).unwrap();
+    assert!(button.is_high().unwrap());
+    gpio.set_mock_input(12, Level::LOW).unwrap();
+    assert!(button.is_low().unwrap());
+
+    gpio.cleanup_all().unwrap();
+    assert!(led.set_high().is_err());
+}
+
+#[test]
+fn test_mock_toggle_pin_7_twice() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+
+    gpio.toggle(vec![7]).unwrap();
+    assert!(gpio.input(7).unwrap() == Level::LOW);
+    gpio.toggle(vec![7]).unwrap();
+    assert!(gpio.input(7).unwrap() == Level::HIGH);
+
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+    assert!(gpio.toggle(vec![12]).is_err());
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_active_low() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.set_active_low(vec![7, 12], true).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+
+    let led = gpio.setup_output_pin(11, None).unwrap();
+
+    // the initial LOW drives the pin high electrically, which reads back as LOW
+    assert!(gpio.input(7).unwrap() == Level::LOW);
+    gpio.output(vec![7], vec![Level::HIGH]).unwrap();
+    assert!(gpio.input(7).unwrap() == Level::HIGH);
+    assert!(gpio.export_config().channels.iter().any(|c| c.active_low));
+
+    gpio.set_mock_input(12, Level::LOW).unwrap();
+    assert!(gpio.input(12).unwrap() == Level::HIGH);
+
+    led.set_high().unwrap();
+    assert!(gpio.input(11).unwrap() == Level::HIGH);
+
+    gpio.cleanup(Some(vec![7])).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    assert!(gpio.input(7).unwrap() == Level::LOW);
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn test_mock_pin_handles_with_embedded_hal() {
+    use embedded_hal::digital::{InputPin, StatefulOutputPin};
+
+    fn blink<P: StatefulOutputPin>(pin: &mut P) {
+        pin.set_high().unwrap();
+        pin.toggle().unwrap();
+    }
+
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    let mut led = gpio.setup_output_pin(7, None).unwrap();
+    blink(&mut led);
+    assert!(led.is_set_low().unwrap());
+
+    let mut button = gpio.setup_input_pin(12, Pull::UP).unwrap();
+    assert!(InputPin::is_high(&mut button).unwrap());
+
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_mock_new_for_model_rejects_unknown_models() {
+    let err = GPIO::new_for_model("JETSON_FOO").err().unwrap();
+    assert!(err.to_string().contains("JETSON_NANO"));
+}
+
+#[test]
+fn test_mock_tegra_soc_channels() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setwarnings(false);
+    gpio.setmode(Mode::TEGRA_SOC).unwrap();
+
+    gpio.setup(vec!["DAP4_SCLK"], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    assert!(gpio.input("DAP4_SCLK").unwrap() == Level::HIGH);
+    assert_eq!(gpio.pin_aliases("DAP4_SCLK").unwrap().board, 12);
+
+    gpio.cleanup(Some(vec!["DAP4_SCLK"])).unwrap();
+    assert!(gpio.input("DAP4_SCLK").is_err());
+}
+
+#[test]
+fn test_mock_blink() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setwarnings(false);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+
+    gpio.blink(7, 2, 1, 1).unwrap();
+    assert!(gpio.input(7).unwrap() == Level::LOW);
+    // only outputs can blink
+    assert!(gpio.blink(12, 1, 1, 1).is_err());
+}
+
+#[test]
+fn test_mock_warning_handler() {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let handler_messages = messages.clone();
+
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.set_warning_handler(move |message| handler_messages.lock().unwrap().push(message.to_string()));
+
+    // nothing has been set up yet
+    gpio.cleanup_all().unwrap();
+    assert_eq!(messages.lock().unwrap().len(), 1);
+
+    gpio.setwarnings(false);
+    gpio.cleanup_all().unwrap();
+    assert_eq!(messages.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_mock_switch_mode_after_cleanup() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+
+    // pin 7 is still set up
+    assert!(gpio.setmode(Mode::BCM).is_err());
+
+    gpio.cleanup(Some(vec![7])).unwrap();
+    gpio.setmode(Mode::BCM).unwrap();
+    assert_eq!(gpio.mode(), Some(Mode::BCM));
+    gpio.setup(vec![4], Direction::OUT, None, None).unwrap();
+}
+
+#[test]
+fn test_mock_cleanup_last_channel_resets_mode() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7, 11], Direction::OUT, None, None).unwrap();
+
+    gpio.cleanup(Some(vec![7])).unwrap();
+    assert_eq!(gpio.getmode(), Some(String::from("BOARD")));
+
+    gpio.cleanup(Some(vec![11])).unwrap();
+    assert_eq!(gpio.getmode(), None);
+    assert!(gpio.setup(vec![7], Direction::OUT, None, None).is_err());
+}
+
+#[test]
+fn test_mock_builder() {
+    let mut gpio = GPIO::builder()
+        .backend(Backend::MOCK)
+        .model_override("JETSON_ORIN_NX")
+        .mode(Mode::BOARD)
+        .warnings(false)
+        .build()
+        .unwrap();
+    assert_eq!(gpio.model, "JETSON_ORIN_NX");
+    assert_eq!(gpio.mode(), Some(Mode::BOARD));
+    gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+
+    assert!(GPIO::builder().backend(Backend::MOCK).model_override("JETSON_FOO").build().is_err());
+}
+
+#[test]
+fn test_mock_debounced_input() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup_input_debounced(12, None, 5).unwrap();
+    gpio.set_mock_input(12, Level::HIGH).unwrap();
+
+    let start = Instant::now();
+    assert_eq!(gpio.input(12).unwrap(), Level::HIGH);
+    assert!(start.elapsed() >= Duration::from_millis(5));
+
+    // a channel with its own interval is sampled at least twice, one interval apart
+    gpio.setup_input_debounced_with_interval(13, None, 5, Duration::from_millis(20)).unwrap();
+    let start = Instant::now();
+    assert_eq!(gpio.input(13).unwrap(), Level::LOW);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    let start = Instant::now();
+    let interval = Duration::from_millis(20);
+    assert!(gpio.wait_for_stable_with_interval(12, Level::HIGH, Duration::from_millis(5), None, interval).unwrap());
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
+
+#[test]
+fn test_mock_output_atomic() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7, 11], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+
+    gpio.output_atomic(vec![7, 11], vec![Level::HIGH, Level::HIGH]).unwrap();
+    assert_eq!(gpio.input_many(vec![7, 11]).unwrap(), vec![Level::HIGH, Level::HIGH]);
+
+    // nothing is written if one of the channels is not an output
+    assert!(gpio.output_atomic(vec![7, 12], vec![Level::LOW]).is_err());
+    assert_eq!(gpio.input(7).unwrap(), Level::HIGH);
+}
+
+#[test]
+fn test_mock_count_pulses_sets_up_input() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    // the mock backend cannot detect edges, but the channel is set up as an input first
+    assert!(gpio.count_pulses(12, Edge::RISING, Duration::from_millis(10)).is_err());
+    assert!(gpio.input(12).is_ok());
+
+    assert!(gpio.measure_frequency(12, Edge::RISING, Duration::ZERO).is_err());
+
+    // an output is not turned into an input
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    let err = gpio.count_pulses(7, Edge::RISING, Duration::from_millis(10)).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<GpioError>(),
+        Some(&GpioError::WRONG_DIRECTION(Channel::Number(7), Direction::IN))
+    );
+    assert!(gpio.output(vec![7], vec![Level::LOW]).is_ok());
+}
+
+#[test]
+fn test_mock_last_output() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::LOW)).unwrap();
+    gpio.setup(vec![11], Direction::OUT, None, None).unwrap();
+    assert_eq!(gpio.last_output(7), Some(Level::LOW));
+    assert_eq!(gpio.last_output(11), None);
+
+    gpio.toggle(vec![7]).unwrap();
+    assert_eq!(gpio.last_output(7), Some(Level::HIGH));
+    gpio.output(vec![11], vec![Level::HIGH]).unwrap();
+    assert_eq!(gpio.last_output(11), Some(Level::HIGH));
+
+    gpio.cleanup(Some(vec![7])).unwrap();
+    assert_eq!(gpio.last_output(7), None);
+}
+
+#[test]
+fn test_mock_typed_errors() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    let error = |result: Result<Level, anyhow::Error>| result.unwrap_err().downcast::<GpioError>().unwrap();
+    assert_eq!(error(gpio.input(7)), GpioError::MODE_NOT_SET);
+
+    gpio.setmode(Mode::BOARD).unwrap();
+    assert_eq!(error(gpio.input(7)), GpioError::NOT_CONFIGURED(Channel::Number(7)));
+    assert_eq!(error(gpio.input(99)), GpioError::INVALID_CHANNEL(Channel::Number(99)));
+
+    gpio.setup(vec![7], Direction::OUT, None, None).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+    let err = gpio.output(vec![12], vec![Level::HIGH]).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<GpioError>(),
+        Some(&GpioError::WRONG_DIRECTION(Channel::Number(12), Direction::OUT))
+    );
+}
+
+#[test]
+fn test_mock_bit_bang_spi() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+    let mut spi = gpio.setup_spi(23, 19, 21, 24, SpiMode::MODE0, BitOrder::MSB_FIRST).unwrap();
+    assert_eq!(gpio.input(24).unwrap(), Level::HIGH);
+
+    assert_eq!(spi.transfer(&[0xa5, 0x5a]).unwrap(), vec![0x00, 0x00]);
+    gpio.set_mock_input(21, Level::HIGH).unwrap();
+    assert_eq!(spi.transfer(&[0x00]).unwrap(), vec![0xff]);
+
+    // CS is released and the clock is back at its idle level after a transfer
+    assert_eq!(gpio.input(24).unwrap(), Level::HIGH);
+    assert_eq!(gpio.input(23).unwrap(), Level::LOW);
+    spi.set_mode(SpiMode::MODE3).unwrap();
+    spi.transfer(&[0x00]).unwrap();
+    assert_eq!(gpio.input(23).unwrap(), Level::HIGH);
+}
+
+#[test]
+fn test_mock_dump_state() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    assert!(gpio.dump_state().is_err());
+
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    gpio.setup(vec![12], Direction::IN, None, None).unwrap();
+
+    let state = gpio.dump_state().unwrap();
+    assert_eq!(state.len(), gpio.available_channels().unwrap().len());
+    let find = |board: u32| state.iter().find(|(channel, _, _)| *channel == Channel::Number(board)).unwrap();
+    assert_eq!(find(7), &(Channel::Number(7), Direction::OUT, Some(Level::HIGH)));
+    assert_eq!(find(12), &(Channel::Number(12), Direction::IN, Some(Level::LOW)));
+    assert_eq!(find(11), &(Channel::Number(11), Direction::UNKNOWN, None));
+}
+
+#[cfg(feature = "stub")]
+#[test]
+fn test_mock_stub_feature_replaces_the_backend() {
+    let mut gpio = GPIO::new();
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::HIGH);
+
+    assert!(GPIO::new_for_model("JETSON_ORIN").is_ok());
+}
+
+#[test]
+fn test_mock_led() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    let mut led = gpio.led(7).unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::LOW);
+    led.on().unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::HIGH);
+    led.off().unwrap();
+    assert_eq!(gpio.input(7).unwrap(), Level::LOW);
+
+    // the mock backend has no PWM, so the LED can only be switched
+    let mut led = gpio.led_pwm(33, 1000.0).unwrap();
+    assert!(!led.is_dimmable());
+    assert!(led.set_brightness(0.5).is_err());
+    led.on().unwrap();
+    assert_eq!(gpio.input(33).unwrap(), Level::HIGH);
+}
+
+#[test]
+fn test_mock_servo_needs_a_pwm_channel() {
+    let mut gpio = GPIO::new_with_backend(Backend::MOCK);
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    // pulses longer than the period are rejected before the channel is touched
+    let config = ServoConfig {
+        frequency_hz: 1000.0,
+        ..ServoConfig::default()
+    };
+    assert!(gpio.servo(33, config).is_err());
+
+    // the mock backend does not simulate PWM controllers, and never touches sysfs
+    let err = gpio.servo(33, ServoConfig::default()).err().unwrap();
+    assert!(err.to_string().contains("is not supported"));
+    assert!(gpio.active_pwm_channels().is_empty());
+
+    // without a PWM controller, an LED is only switched on and off
+    let mut led = gpio.led_pwm(33, 1000.0).unwrap();
+    assert!(!led.is_dimmable());
+    led.on().unwrap();
+    assert_eq!(gpio.input(33).unwrap(), Level::HIGH);
+}
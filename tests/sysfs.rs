@@ -0,0 +1,263 @@
+// The stub feature replaces the sysfs backend with the mock one
+#![cfg(not(feature = "stub"))]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use jetson_gpio::{
+    Backend, Channel, ChannelSnapshot, ConfigSnapshot, Direction, Drive, GpioError, GpioPaths, Level, Mode, GPIO,
+};
+
+// The locations apply to the whole process, so these tests live apart from the mock ones and
+// hold this lock while they use them
+static PATHS_LOCK: Mutex<()> = Mutex::new(());
+
+// Creates a sysfs and device tree of a Jetson AGX Orin in which channel 7 (PQ.06) is already exported
+// as an input.
+fn fake_orin(name: &str) -> (MutexGuard<'static, ()>, PathBuf, GpioPaths) {
+    let lock = PATHS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let root = std::env::temp_dir().join(format!("jetson-gpio-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    let write = |path: &str, contents: &str| {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    };
+    write("proc/device-tree/compatible", "nvidia,p3737-0000+p3701-0000\0nvidia,tegra234\0");
+    write("sys/devices/2200000.gpio/gpio/gpiochip348/base", "348\n");
+    write("sys/devices/2200000.gpio/gpio/gpiochip348/ngpio", "164\n");
+    write("sys/class/gpio/export", "");
+    write("sys/class/gpio/unexport", "");
+    write("sys/class/gpio/PQ.06/direction", "in\n");
+    write("sys/class/gpio/PQ.06/value", "0\n");
+
+    let path = |p: &str| root.join(p).to_string_lossy().into_owned();
+    let paths = GpioPaths {
+        sysfs: path("sys"),
+        device_tree: path("proc/device-tree"),
+        dev: path("dev"),
+    };
+
+    (lock, root, paths)
+}
+
+fn read(root: &Path, path: &str) -> String {
+    fs::read_to_string(root.join(path)).unwrap().trim().to_string()
+}
+
+#[test]
+fn test_sysfs_output_with_initial_never_floats() {
+    let (_lock, root, paths) = fake_orin("initial");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    // the direction and the level are set by a single write, and the value is never written
+    // separately, so the pin goes from input straight to driving HIGH
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/direction"), "high");
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/value"), "0");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_open_drain_floats_for_high() {
+    let (_lock, root, paths) = fake_orin("open-drain");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    gpio.setup_output_with_drive(vec![7], Drive::OPEN_DRAIN, Some(Level::LOW)).unwrap();
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/direction"), "low");
+    // a sysfs attribute is replaced by every write, the regular file here must be emptied
+    fs::write(root.join("sys/class/gpio/PQ.06/direction"), "").unwrap();
+    gpio.output(vec![7], vec![Level::HIGH]).unwrap();
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/direction"), "in");
+    gpio.output(vec![7], vec![Level::LOW]).unwrap();
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/direction"), "low");
+    // the value file is never written, the level comes from the direction alone
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/value"), "0");
+
+    // the value file is not even opened
+    fs::remove_file(root.join("sys/class/gpio/PQ.06/value")).unwrap();
+    fs::write(root.join("sys/class/gpio/PQ.06/direction"), "").unwrap();
+    gpio.output_atomic(vec![7], vec![Level::HIGH]).unwrap();
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/direction"), "in");
+    assert_eq!(gpio.last_output(7), Some(Level::HIGH));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_wait_for_level_returns_at_once_if_reached() {
+    let (_lock, root, paths) = fake_orin("wait-level");
+    fs::write(root.join("sys/class/gpio/PQ.06/edge"), "none").unwrap();
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::IN, None, None).unwrap();
+
+    let timeout = Some(Duration::from_millis(20));
+    assert!(gpio.wait_for_level(7, Level::LOW, timeout).unwrap());
+    assert!(!gpio.wait_for_level(7, Level::HIGH, timeout).unwrap());
+    // edge detection is disabled again afterwards, the regular file keeps the tail of "rising"
+    assert!(read(&root, "sys/class/gpio/PQ.06/edge").starts_with("none"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_reset_unexports_channels_of_other_processes() {
+    let (_lock, root, paths) = fake_orin("reset");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    // PQ.06 is exported, but not by this object, so only reset unexports it
+    gpio.reset(vec![7]).unwrap();
+    assert_eq!(read(&root, "sys/class/gpio/unexport"), "454");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_dump_state_includes_channels_of_other_processes() {
+    let (_lock, root, paths) = fake_orin("dump");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    let state = gpio.dump_state().unwrap();
+    assert!(state.contains(&(Channel::Number(7), Direction::IN, Some(Level::LOW))));
+    assert!(state.contains(&(Channel::Number(11), Direction::UNKNOWN, None)));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_failed_direction_writes_are_returned() {
+    let (_lock, root, paths) = fake_orin("direction-error");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+
+    // a directory in place of the attribute cannot be opened for writing
+    let direction = root.join("sys/class/gpio/PQ.06/direction");
+    fs::remove_file(&direction).unwrap();
+    fs::create_dir(&direction).unwrap();
+    assert!(gpio.setup(vec![7], Direction::IN, None, None).is_err());
+    assert!(gpio.setup_output_with_drive(vec![7], Drive::OPEN_DRAIN, None).is_err());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_failed_value_reads_are_returned() {
+    let (_lock, root, paths) = fake_orin("value-error");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::IN, None, None).unwrap();
+
+    // the channel is unexported behind the back of the object
+    fs::remove_file(root.join("sys/class/gpio/PQ.06/value")).unwrap();
+    assert!(gpio.input(7).is_err());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_write_access_is_checked_up_front() {
+    let (_lock, root, paths) = fake_orin("access");
+    let gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    assert!(gpio.has_write_access());
+
+    let export = root.join("sys/class/gpio/export");
+    let mut permissions = fs::metadata(&export).unwrap().permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&export, permissions).unwrap();
+    assert!(!gpio.has_write_access());
+    assert_eq!(gpio.check_write_access(), Err(GpioError::NO_WRITE_ACCESS));
+
+    fs::remove_file(&export).unwrap();
+    let missing = gpio.check_write_access();
+    assert!(matches!(missing, Err(GpioError::NO_INTERFACE(path)) if path.ends_with("sys/class/gpio")));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_watchdog_reports_failed_writes() {
+    let (_lock, root, paths) = fake_orin("watchdog");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let sink = warnings.clone();
+    gpio.set_warning_handler(move |message| sink.lock().unwrap().push(message.to_string()));
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+
+    // the value file cannot be opened for writing while it is a directory
+    let value = root.join("sys/class/gpio/PQ.06/value");
+    fs::remove_file(&value).unwrap();
+    fs::create_dir(&value).unwrap();
+    gpio.enable_watchdog(Duration::from_millis(20), vec![(7, Level::LOW)]).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    let failed = |warnings: &Mutex<Vec<String>>| {
+        let warnings = warnings.lock().unwrap();
+        warnings.iter().filter(|w| w.starts_with("The watchdog failed to drive channel 7")).count()
+    };
+    assert_eq!(failed(&warnings), 1);
+    assert_eq!(gpio.last_output(7), Some(Level::HIGH));
+
+    fs::remove_dir(&value).unwrap();
+    fs::write(&value, "1").unwrap();
+    gpio.kick_watchdog().unwrap();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(failed(&warnings), 1);
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/value"), "0");
+    assert_eq!(gpio.last_output(7), Some(Level::LOW));
+
+    gpio.disable_watchdog();
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_watched_channel_stays_driven() {
+    let (_lock, root, paths) = fake_orin("tristate");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7], Direction::OUT, None, Some(Level::HIGH)).unwrap();
+
+    gpio.enable_watchdog(Duration::from_secs(10), vec![(7, Level::LOW)]).unwrap();
+    assert!(gpio.set_tristate(7).is_err());
+    assert_eq!(read(&root, "sys/class/gpio/PQ.06/direction"), "high");
+
+    gpio.disable_watchdog();
+    gpio.set_tristate(7).unwrap();
+    assert!(read(&root, "sys/class/gpio/PQ.06/direction").starts_with("in"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_sysfs_import_config_restores_labels() {
+    let (_lock, root, paths) = fake_orin("import-config");
+    let mut gpio = GPIO::new_with_paths(paths, Backend::SYSFS).unwrap();
+
+    // channel 7 was left exported as an input by a previous process
+    let snapshot = ConfigSnapshot {
+        mode: Some(Mode::BOARD),
+        channels: vec![ChannelSnapshot {
+            channel: Channel::Number(7),
+            direction: Direction::IN,
+            active_low: false,
+            label: Some(String::from("door sensor")),
+        }],
+    };
+    gpio.import_config(snapshot).unwrap();
+    assert_eq!(gpio.label(7), Some("door sensor"));
+    assert_eq!(gpio.export_config().channels[0].label.as_deref(), Some("door sensor"));
+
+    gpio.cleanup(Some(vec![7])).unwrap();
+    assert_eq!(gpio.label(7), None);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -5,7 +5,7 @@ use jetson_gpio::{GPIO, Direction, Level, Mode};
 fn test_flash_leds_pin_7_11() {
     let mut gpio = GPIO::new();
     gpio.setmode(Mode::BOARD).unwrap();
-    gpio.setup(vec![7, 11], Direction::OUT, Some(Level::LOW)).unwrap();
+    gpio.setup(vec![7, 11], Direction::OUT, None, Some(Level::LOW)).unwrap();
 
     for _ in 0..2 {
         std::thread::sleep(std::time::Duration::from_secs(1));
@@ -14,5 +14,19 @@ fn test_flash_leds_pin_7_11() {
         gpio.output(vec![7, 11], vec![Level::LOW, Level::LOW]).unwrap();
     }
 
-    gpio.cleanup(None).unwrap();
+    gpio.cleanup_all().unwrap();
+}
+
+#[test]
+fn test_output_one_value_to_pins_7_11_13() {
+    let mut gpio = GPIO::new();
+    gpio.setmode(Mode::BOARD).unwrap();
+    gpio.setup(vec![7, 11, 13], Direction::OUT, None, Some(Level::LOW)).unwrap();
+
+    gpio.output(vec![7, 11, 13], vec![Level::HIGH]).unwrap();
+    for pin in [7, 11, 13] {
+        assert!(gpio.input(pin).unwrap() == Level::HIGH);
+    }
+
+    gpio.cleanup_all().unwrap();
 }